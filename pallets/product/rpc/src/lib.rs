@@ -0,0 +1,117 @@
+//! JSON-RPC methods for querying products, backed by the `ProductApi` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_product_runtime_api::{ProductApi as ProductRuntimeApi, PositionKind, ProductInfo};
+
+#[rpc(client, server)]
+pub trait ProductApi<BlockHash, AccountId, Balance, AssetId> {
+	#[method(name = "product_getProduct")]
+	fn get_product(
+		&self,
+		id: u128,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ProductInfo<AccountId, Balance, AssetId>>>;
+
+	#[method(name = "product_listByOwner")]
+	fn list_by_owner(&self, owner: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<u128>>;
+
+	#[method(name = "product_listByPosition")]
+	fn list_by_position(
+		&self,
+		kind: PositionKind,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<u128>>;
+}
+
+/// Provides RPC methods to query products.
+pub struct Product<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Product<C, B> {
+	/// Creates a new instance of the Product RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query products.",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance, AssetId>
+	ProductApiServer<<Block as BlockT>::Hash, AccountId, Balance, AssetId> for Product<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ProductRuntimeApi<Block, AccountId, Balance, AssetId>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	AssetId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn get_product(
+		&self,
+		id: u128,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<ProductInfo<AccountId, Balance, AssetId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_product(&at, id).map_err(runtime_error)
+	}
+
+	fn list_by_owner(
+		&self,
+		owner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<u128>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.list_by_owner(&at, owner).map_err(runtime_error)
+	}
+
+	fn list_by_position(
+		&self,
+		kind: PositionKind,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<u128>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.list_by_position(&at, kind).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,23 @@
+//! Runtime API for querying products stored by pallet-product, served over JSON-RPC by
+//! pallet-product-rpc.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_product::types::{PaymentAsset, PositionKind, ProductInfo, ProductPositionEnum};
+
+sp_api::decl_runtime_apis! {
+	pub trait ProductApi<AccountId, Balance, AssetId> where
+		AccountId: Codec,
+		Balance: Codec,
+		AssetId: Codec,
+	{
+		fn get_product(id: u128) -> Option<ProductInfo<AccountId, Balance, AssetId>>;
+
+		fn list_by_owner(owner: AccountId) -> Vec<u128>;
+
+		fn list_by_position(kind: PositionKind) -> Vec<u128>;
+	}
+}
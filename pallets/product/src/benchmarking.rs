@@ -1,20 +1,676 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-product
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as ProductModule;
+use crate::types::{
+	AuctionKind, BalanceOf, ClaimInfo, ClaimOutcome, EscrowInfo, MaxPayouts, OfferInfo, PaymentAsset, PayoutSplits,
+	Product, ProductAction, ProductHistoryEntry, ProductMetadata, ProductName,
+	ProductPositionEnum, RecallTarget, Role, Roles, SaleInfo,
+};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+	BoundedVec,
+};
 use frame_system::RawOrigin;
+use sp_runtime::{
+	traits::{Bounded, Zero},
+	Perbill,
+};
+use sp_std::{vec, vec::Vec};
 
-// benchmarks! {
-// 	do_something {
-// 		let s in 0 .. 100;
-// 		let caller: T::AccountId = whitelisted_caller();
-// 	}: _(RawOrigin::Signed(caller), s)
-// 	verify {
-// 		assert_eq!(Something::<T>::get(), Some(s));
-// 	}
-
-// 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
-// }
+const SEED: u32 = 0;
+
+fn all_roles() -> Roles {
+	let mut roles = Roles::default();
+	for role in [Role::Manufacturer, Role::Distributor, Role::Retailer, Role::Auditor, Role::Admin] {
+		roles.insert(role);
+	}
+	roles
+}
+
+fn funded<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 100u32.into());
+}
+
+// the whitelisted caller holding every role, with funds for deposits and purchases
+fn authorized_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	AuthorizedUsers::<T>::insert(&caller, all_roles());
+	funded::<T>(&caller);
+	caller
+}
+
+fn max_name<T: Config>() -> ProductName<T> {
+	BoundedVec::try_from(vec![b'p'; T::MaxNameLength::get() as usize]).unwrap()
+}
+
+fn set_owner_account<T: Config>() {
+	let server: T::AccountId = account("server", 0, SEED);
+	funded::<T>(&server);
+	ProductOwnerAccount::<T>::put(server);
+}
+
+// every split slot used by a funded account, sharing the first sale with the seller
+fn max_splits<T: Config>() -> PayoutSplits<T> {
+	let count = T::MaxSplits::get();
+	let splits: Vec<_> = (0..count)
+		.map(|i| {
+			let payee: T::AccountId = account("payee", i, SEED);
+			funded::<T>(&payee);
+			(payee, Perbill::from_rational(1, count + 1))
+		})
+		.collect();
+	BoundedVec::try_from(splits).unwrap()
+}
+
+// adds a lot of `quantity` units and returns its id
+fn add_lot<T: Config>(owner: &T::AccountId, quantity: u32) -> u128 {
+	ProductModule::<T>::do_add_product(
+		owner.clone(),
+		max_name::<T>(),
+		100u32.into(),
+		quantity,
+		Some(max_splits::<T>()),
+		Some(100u32.into()),
+	)
+	.unwrap();
+	ProductCounter::<T>::get().unwrap()
+}
+
+fn update<T: Config>(id: u128, f: impl FnOnce(&mut Product<T>)) {
+	let mut p = Products::<T>::get(id).unwrap();
+	f(&mut p);
+	ProductModule::<T>::put_product(id, &p);
+}
+
+// history at its bound so the next entry evicts the oldest one
+fn fill_history<T: Config>(id: u128, actor: &T::AccountId) {
+	let p = Products::<T>::get(id).unwrap();
+	let entries: Vec<ProductHistoryEntry<T>> = (0..T::MaxHistoryLength::get())
+		.map(|_| {
+			ProductHistoryEntry::<T>::new(
+				frame_system::Pallet::<T>::block_number(),
+				actor.clone(),
+				ProductAction::PositionUpdated,
+				&p,
+			)
+		})
+		.collect();
+	ProductHistory::<T>::insert(id, BoundedVec::try_from(entries).unwrap());
+}
+
+// lists a sold product for resale until `expiry`
+fn list<T: Config>(id: u128, expiry: T::BlockNumber) {
+	update::<T>(id, |p| p.set_resale(true));
+	ResaleDeadlines::<T>::mutate(expiry, |ids| ids.try_push(id)).unwrap();
+	ResaleExpiries::<T>::insert(id, expiry);
+}
+
+// an offer of 100 from `bidder`, expiring at `expiry`
+fn place_offer<T: Config>(id: u128, bidder: &T::AccountId, expiry: T::BlockNumber) {
+	let amount: BalanceOf<T> = 100u32.into();
+	T::Currency::reserve(bidder, amount).unwrap();
+	OfferDeadlines::<T>::mutate(expiry, |offers| offers.try_push((id, bidder.clone()))).unwrap();
+	Offers::<T>::insert(id, bidder, OfferInfo::<T> { amount, asset: PaymentAsset::Native, expiry });
+	OfferCounts::<T>::mutate(id, |n| *n += 1);
+}
+
+// `buyer` bought product `id` from `seller` in this block, every payout went to a funded account
+fn record_sale<T: Config>(id: u128, buyer: &T::AccountId, seller: &T::AccountId) {
+	let payouts: Vec<_> = (0..MaxPayouts::<T>::get())
+		.map(|i| {
+			let payee: T::AccountId = account("payee", i, SEED);
+			funded::<T>(&payee);
+			(payee, BalanceOf::<T>::from(10u32))
+		})
+		.collect();
+	LastSales::<T>::insert(
+		id,
+		SaleInfo::<T> {
+			buyer: buyer.clone(),
+			seller: seller.clone(),
+			asset: PaymentAsset::Native,
+			payouts: BoundedVec::try_from(payouts).unwrap(),
+			first_sale: true,
+		},
+	);
+	update::<T>(id, |p| {
+		p.set_owner(buyer.clone());
+		p.set_is_sold(true);
+		p.set_purchased_at(Some(frame_system::Pallet::<T>::block_number()));
+	});
+}
+
+// the buyer of the last sale of `id` asked to return it in this block
+fn open_return<T: Config>(id: u128) {
+	let deadline = frame_system::Pallet::<T>::block_number() + T::ReturnResponsePeriod::get();
+	ReturnDeadlines::<T>::mutate(deadline, |ids| ids.try_push(id)).unwrap();
+	ReturnRequests::<T>::insert(id, deadline);
+}
+
+fn open_escrow<T: Config>(id: u128, buyer: &T::AccountId, seller: &T::AccountId, shipped: bool) {
+	let amount: BalanceOf<T> = 100u32.into();
+	T::Currency::reserve(buyer, amount).unwrap();
+	let deadline = frame_system::Pallet::<T>::block_number() + T::EscrowTimeout::get();
+	EscrowDeadlines::<T>::mutate(deadline, |ids| ids.try_push(id)).unwrap();
+	Escrows::<T>::insert(
+		id,
+		EscrowInfo::<T> {
+			buyer: buyer.clone(),
+			seller: seller.clone(),
+			amount,
+			asset: PaymentAsset::Native,
+			deadline,
+			shipped,
+		},
+	);
+}
+
+benchmarks! {
+	add_authorized_user {
+		let origin = T::AdminOrigin::successful_origin();
+		let new_user: T::AccountId = account("user", 0, SEED);
+	}: _<T::RuntimeOrigin>(origin, new_user.clone())
+	verify {
+		assert!(AuthorizedUsers::<T>::contains_key(&new_user));
+	}
+
+	add_product {
+		let caller = authorized_caller::<T>();
+	}: _(RawOrigin::Signed(caller), max_name::<T>(), 100u32.into(), Some(max_splits::<T>()), Some(100u32.into()))
+	verify {
+		assert_eq!(ProductCounter::<T>::get(), Some(1));
+	}
+
+	add_batch {
+		let caller = authorized_caller::<T>();
+	}: _(
+		RawOrigin::Signed(caller),
+		max_name::<T>(),
+		100u32.into(),
+		1_000,
+		Some(max_splits::<T>()),
+		Some(100u32.into())
+	)
+	verify {
+		assert_eq!(ProductCounter::<T>::get(), Some(1));
+	}
+
+	split_batch {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1_000);
+		fill_history::<T>(id, &caller);
+	}: _(RawOrigin::Signed(caller), id, 500)
+	verify {
+		assert_eq!(ProductCounter::<T>::get(), Some(2));
+	}
+
+	update_position {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		update::<T>(id, |p| p.set_position(ProductPositionEnum::Distribution));
+		fill_history::<T>(id, &caller);
+	}: _(RawOrigin::Signed(caller), id, ProductPositionEnum::Retailer)
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_position(), ProductPositionEnum::Retailer);
+	}
+
+	// buying part of a lot, which splits the bought units into a new product
+	buy_product {
+		set_owner_account::<T>();
+		let seller = authorized_caller::<T>();
+		let id = add_lot::<T>(&seller, 2);
+		update::<T>(id, |p| p.set_position(ProductPositionEnum::Retailer));
+		fill_history::<T>(id, &seller);
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		funded::<T>(&buyer);
+	}: _(RawOrigin::Signed(buyer), id, 1)
+	verify {
+		assert_eq!(ProductCounter::<T>::get(), Some(2));
+	}
+
+	// relisting moves the listing to a new expiry block
+	enable_resale {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &caller);
+		list::<T>(id, 10u32.into());
+	}: _(RawOrigin::Signed(caller), id, 200u32.into(), Some(20u32.into()))
+	verify {
+		assert!(Products::<T>::get(id).unwrap().get_resale());
+		assert_eq!(ResaleExpiries::<T>::get(id), Some(20u32.into()));
+	}
+
+	disable_resale {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &caller);
+		list::<T>(id, 10u32.into());
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert!(!Products::<T>::get(id).unwrap().get_resale());
+		assert!(!ResaleExpiries::<T>::contains_key(id));
+	}
+
+	update_resale_price {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &caller);
+		list::<T>(id, 10u32.into());
+	}: _(RawOrigin::Signed(caller), id, 300u32.into())
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_price(), 300u32.into());
+	}
+
+	grant_role {
+		let origin = T::AdminOrigin::successful_origin();
+		let who: T::AccountId = account("user", 0, SEED);
+	}: _<T::RuntimeOrigin>(origin, who.clone(), Role::Admin)
+	verify {
+		assert!(AuthorizedUsers::<T>::get(&who).contains(Role::Admin));
+	}
+
+	revoke_role {
+		let origin = T::AdminOrigin::successful_origin();
+		let who: T::AccountId = account("user", 0, SEED);
+		AuthorizedUsers::<T>::insert(&who, all_roles());
+	}: _<T::RuntimeOrigin>(origin, who.clone(), Role::Admin)
+	verify {
+		assert!(!AuthorizedUsers::<T>::get(&who).contains(Role::Admin));
+	}
+
+	remove_authorized_user {
+		let origin = T::AdminOrigin::successful_origin();
+		let who: T::AccountId = account("user", 0, SEED);
+		AuthorizedUsers::<T>::insert(&who, all_roles());
+	}: _<T::RuntimeOrigin>(origin, who.clone())
+	verify {
+		assert!(!AuthorizedUsers::<T>::contains_key(&who));
+	}
+
+	suspend_authorized_user {
+		let origin = T::AdminOrigin::successful_origin();
+		let who: T::AccountId = account("user", 0, SEED);
+		AuthorizedUsers::<T>::insert(&who, all_roles());
+	}: _<T::RuntimeOrigin>(origin, who.clone(), 100u32.into())
+	verify {
+		assert!(SuspendedUsers::<T>::contains_key(&who));
+	}
+
+	mark_shipped {
+		let seller = authorized_caller::<T>();
+		let id = add_lot::<T>(&seller, 1);
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		funded::<T>(&buyer);
+		open_escrow::<T>(id, &buyer, &seller, false);
+	}: _(RawOrigin::Signed(seller), id)
+	verify {
+		assert!(Escrows::<T>::get(id).unwrap().shipped);
+	}
+
+	confirm_delivery {
+		set_owner_account::<T>();
+		let seller = authorized_caller::<T>();
+		let id = add_lot::<T>(&seller, 1);
+		fill_history::<T>(id, &seller);
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		funded::<T>(&buyer);
+		open_escrow::<T>(id, &buyer, &seller, true);
+	}: _(RawOrigin::Signed(buyer.clone()), id)
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_owner(), buyer);
+	}
+
+	// removing a product holding the most offers, each given back to its bidder, and the most
+	// warranty claims
+	remove_product {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		fill_history::<T>(id, &caller);
+		for i in 0..T::MaxOffersPerProduct::get() {
+			let bidder: T::AccountId = account("bidder", i, SEED);
+			funded::<T>(&bidder);
+			place_offer::<T>(id, &bidder, (10u32 + i).into());
+		}
+		let claims = T::MaxClaimsPerProduct::get();
+		for claim in 0..claims {
+			WarrantyClaims::<T>::insert(id, claim, ClaimInfo::<T> {
+				claimant: caller.clone(),
+				evidence_hash: T::Hash::default(),
+				filed_at: frame_system::Pallet::<T>::block_number(),
+				outcome: Some(ClaimOutcome::Repair),
+			});
+		}
+		ClaimCounts::<T>::insert(id, claims);
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert!(!Products::<T>::contains_key(id));
+		assert_eq!(Offers::<T>::iter_prefix(id).count(), 0);
+		assert_eq!(WarrantyClaims::<T>::iter_prefix(id).count(), 0);
+	}
+
+	update_metadata {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		let metadata = ProductMetadata::<T> {
+			sku: BoundedVec::try_from(vec![b's'; 32]).unwrap(),
+			gtin: Some(BoundedVec::try_from(b"04006381333931".to_vec()).unwrap()),
+			description: BoundedVec::try_from(vec![b'd'; T::MaxDescriptionLength::get() as usize]).unwrap(),
+			category: BoundedVec::try_from(vec![b'c'; 32]).unwrap(),
+			content_id: Some(BoundedVec::try_from(vec![b'q'; 64]).unwrap()),
+		};
+	}: _(RawOrigin::Signed(caller), id, metadata)
+	verify {
+		assert!(ProductMetadataOf::<T>::contains_key(id));
+	}
+
+	set_price_asset {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), id, PaymentAsset::Native)
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_asset(), PaymentAsset::Native);
+	}
+
+	// transferring a listed product withdraws the listing
+	transfer_product {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &caller);
+		list::<T>(id, 10u32.into());
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(caller), id, to.clone())
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_owner(), to);
+	}
+
+	propose_transfer {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(caller), id, to.clone())
+	verify {
+		assert_eq!(PendingTransfers::<T>::get(id), Some(to));
+	}
+
+	accept_transfer {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &caller);
+		list::<T>(id, 10u32.into());
+		let to: T::AccountId = account("to", 0, SEED);
+		PendingTransfers::<T>::insert(id, &to);
+	}: _(RawOrigin::Signed(to.clone()), id)
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_owner(), to);
+	}
+
+	cancel_transfer {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		let to: T::AccountId = account("to", 0, SEED);
+		PendingTransfers::<T>::insert(id, &to);
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(id));
+	}
+
+	// replacing a previous offer of the same bidder
+	make_offer {
+		let owner = authorized_caller::<T>();
+		let id = add_lot::<T>(&owner, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		let bidder: T::AccountId = account("bidder", 0, SEED);
+		funded::<T>(&bidder);
+		place_offer::<T>(id, &bidder, 10u32.into());
+	}: _(RawOrigin::Signed(bidder.clone()), id, 200u32.into(), 20u32.into())
+	verify {
+		assert_eq!(Offers::<T>::get(id, &bidder).unwrap().amount, 200u32.into());
+	}
+
+	// accepting an offer on a listed product, paying the royalty and withdrawing the listing
+	accept_offer {
+		let owner = authorized_caller::<T>();
+		let id = add_lot::<T>(&owner, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &owner);
+		list::<T>(id, 10u32.into());
+		let bidder: T::AccountId = account("bidder", 0, SEED);
+		funded::<T>(&bidder);
+		place_offer::<T>(id, &bidder, 10u32.into());
+	}: _(RawOrigin::Signed(owner), id, bidder.clone())
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_owner(), bidder);
+	}
+
+	reject_offer {
+		let owner = authorized_caller::<T>();
+		let id = add_lot::<T>(&owner, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		let bidder: T::AccountId = account("bidder", 0, SEED);
+		funded::<T>(&bidder);
+		place_offer::<T>(id, &bidder, 10u32.into());
+	}: _(RawOrigin::Signed(owner), id, bidder.clone())
+	verify {
+		assert!(!Offers::<T>::contains_key(id, &bidder));
+	}
+
+	// starting on a listed product withdraws the listing
+	start_auction {
+		let caller = authorized_caller::<T>();
+		let id = add_lot::<T>(&caller, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &caller);
+		list::<T>(id, 10u32.into());
+	}: _(RawOrigin::Signed(caller), id, AuctionKind::English, 100u32.into(), 10u32.into())
+	verify {
+		assert!(Auctions::<T>::contains_key(id));
+	}
+
+	// outbidding returns the reserve of the previous best bid
+	bid_english {
+		let owner = authorized_caller::<T>();
+		let id = add_lot::<T>(&owner, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		ProductModule::<T>::start_auction(
+			RawOrigin::Signed(owner).into(), id, AuctionKind::English, 100u32.into(), 10u32.into()
+		)?;
+		let outbid: T::AccountId = account("bidder", 0, SEED);
+		funded::<T>(&outbid);
+		ProductModule::<T>::bid(RawOrigin::Signed(outbid).into(), id, 100u32.into())?;
+		let bidder: T::AccountId = account("bidder", 1, SEED);
+		funded::<T>(&bidder);
+	}: bid(RawOrigin::Signed(bidder.clone()), id, 200u32.into())
+	verify {
+		assert_eq!(Auctions::<T>::get(id).unwrap().best_bid, Some((bidder, 200u32.into())));
+	}
+
+	// a Dutch bid buys the product at once, paying the fee, the royalty and the seller
+	bid_dutch {
+		set_owner_account::<T>();
+		let owner = authorized_caller::<T>();
+		let id = add_lot::<T>(&owner, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &owner);
+		ProductModule::<T>::start_auction(
+			RawOrigin::Signed(owner).into(), id, AuctionKind::Dutch, 50u32.into(), 10u32.into()
+		)?;
+		let bidder: T::AccountId = account("bidder", 0, SEED);
+		funded::<T>(&bidder);
+	}: bid(RawOrigin::Signed(bidder.clone()), id, 200u32.into())
+	verify {
+		assert!(!Auctions::<T>::contains_key(id));
+		assert_eq!(Products::<T>::get(id).unwrap().get_owner(), bidder);
+	}
+
+	settle_auction {
+		let owner = authorized_caller::<T>();
+		let id = add_lot::<T>(&owner, 1);
+		update::<T>(id, |p| p.set_is_sold(true));
+		fill_history::<T>(id, &owner);
+		ProductModule::<T>::start_auction(
+			RawOrigin::Signed(owner).into(), id, AuctionKind::English, 100u32.into(), 10u32.into()
+		)?;
+		let bidder: T::AccountId = account("bidder", 0, SEED);
+		funded::<T>(&bidder);
+		ProductModule::<T>::bid(RawOrigin::Signed(bidder.clone()).into(), id, 100u32.into())?;
+		frame_system::Pallet::<T>::set_block_number(100u32.into());
+	}: _(RawOrigin::Signed(bidder.clone()), id)
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_owner(), bidder);
+	}
+
+	// a batch of resold products, each in escrow or in auction with a best bid, every escrow
+	// refunded, every bid returned and every owner refunded out of the recall deposit
+	recall_product {
+		let n in 1 .. T::MaxRecallBatch::get();
+		let caller = authorized_caller::<T>();
+		let owner: T::AccountId = account("owner", 0, SEED);
+		funded::<T>(&owner);
+		let mut ids = Vec::new();
+		for i in 0..n {
+			let id = add_lot::<T>(&caller, 1);
+			update::<T>(id, |p| {
+				p.set_is_sold(true);
+				p.set_owner(owner.clone());
+			});
+			SalePrices::<T>::insert(id, BalanceOf::<T>::from(100u32));
+			fill_history::<T>(id, &caller);
+			let buyer: T::AccountId = account("buyer", i, SEED);
+			funded::<T>(&buyer);
+			// every escrow and auction ends in a block of its own
+			frame_system::Pallet::<T>::set_block_number((i + 1).into());
+			if i % 2 == 0 {
+				open_escrow::<T>(id, &buyer, &owner, false);
+			} else {
+				ProductModule::<T>::start_auction(
+					RawOrigin::Signed(owner.clone()).into(), id, AuctionKind::English, 100u32.into(), 10u32.into()
+				)?;
+				ProductModule::<T>::bid(RawOrigin::Signed(buyer).into(), id, 100u32.into())?;
+			}
+			ids.push(id);
+		}
+		let deposit: BalanceOf<T> = (100u32 * n).into();
+		T::Currency::reserve(&caller, deposit)?;
+		RecallDeposits::<T>::insert(&caller, deposit);
+		let target = RecallTarget::<T>::Batch(BoundedVec::try_from(ids).unwrap());
+	}: _(RawOrigin::Signed(caller.clone()), target, T::Hash::default(), true)
+	verify {
+		assert_eq!(Recalls::<T>::iter().count() as u32, n);
+		assert_eq!(Escrows::<T>::iter().count(), 0);
+		assert_eq!(Auctions::<T>::iter().count(), 0);
+		assert!(RecallDeposits::<T>::get(&caller).is_zero());
+	}
+
+	reserve_recall_deposit {
+		let caller = authorized_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), 100u32.into())
+	verify {
+		assert_eq!(RecallDeposits::<T>::get(&caller), 100u32.into());
+	}
+
+	release_recall_deposit {
+		let caller = authorized_caller::<T>();
+		T::Currency::reserve(&caller, 100u32.into())?;
+		RecallDeposits::<T>::insert(&caller, BalanceOf::<T>::from(100u32));
+	}: _(RawOrigin::Signed(caller.clone()), 100u32.into())
+	verify {
+		assert!(RecallDeposits::<T>::get(&caller).is_zero());
+	}
+
+	set_product_owner_account {
+		let origin = T::AdminOrigin::successful_origin();
+		let account: T::AccountId = account("treasury", 0, SEED);
+	}: _<T::RuntimeOrigin>(origin, account.clone())
+	verify {
+		assert_eq!(ProductOwnerAccount::<T>::get(), Some(account));
+	}
+
+	// the request withdraws a resale listing
+	request_return {
+		let seller = authorized_caller::<T>();
+		let id = add_lot::<T>(&seller, 1);
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		record_sale::<T>(id, &buyer, &seller);
+		fill_history::<T>(id, &buyer);
+		list::<T>(id, 10u32.into());
+	}: _(RawOrigin::Signed(buyer), id)
+	verify {
+		assert!(ReturnRequests::<T>::contains_key(id));
+	}
+
+	approve_return {
+		let seller = authorized_caller::<T>();
+		let id = add_lot::<T>(&seller, 1);
+		fill_history::<T>(id, &seller);
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		funded::<T>(&buyer);
+		record_sale::<T>(id, &buyer, &seller);
+		open_return::<T>(id);
+	}: _(RawOrigin::Signed(seller.clone()), id)
+	verify {
+		assert_eq!(Products::<T>::get(id).unwrap().get_owner(), seller);
+	}
+
+	reject_return {
+		let seller = authorized_caller::<T>();
+		let id = add_lot::<T>(&seller, 1);
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		record_sale::<T>(id, &buyer, &seller);
+		open_return::<T>(id);
+	}: _(RawOrigin::Signed(seller), id)
+	verify {
+		assert!(!ReturnRequests::<T>::contains_key(id));
+	}
+
+	// the previous claim is resolved, so a new one can be filed
+	file_warranty_claim {
+		let seller = authorized_caller::<T>();
+		let id = add_lot::<T>(&seller, 1);
+		let owner: T::AccountId = account("buyer", 0, SEED);
+		update::<T>(id, |p| {
+			p.set_owner(owner.clone());
+			p.set_is_sold(true);
+			p.set_warranty_expiry(Some(100u32.into()));
+		});
+		fill_history::<T>(id, &owner);
+		WarrantyClaims::<T>::insert(id, 0, ClaimInfo::<T> {
+			claimant: owner.clone(),
+			evidence_hash: T::Hash::default(),
+			filed_at: frame_system::Pallet::<T>::block_number(),
+			outcome: Some(ClaimOutcome::Repair),
+		});
+		ClaimCounts::<T>::insert(id, 1);
+	}: _(RawOrigin::Signed(owner), id, T::Hash::default())
+	verify {
+		assert_eq!(ClaimCounts::<T>::get(id), 2);
+	}
+
+	resolve_claim {
+		let seller = authorized_caller::<T>();
+		let id = add_lot::<T>(&seller, 1);
+		fill_history::<T>(id, &seller);
+		let owner: T::AccountId = account("buyer", 0, SEED);
+		WarrantyClaims::<T>::insert(id, 0, ClaimInfo::<T> {
+			claimant: owner,
+			evidence_hash: T::Hash::default(),
+			filed_at: frame_system::Pallet::<T>::block_number(),
+			outcome: None,
+		});
+		ClaimCounts::<T>::insert(id, 1);
+	}: _(RawOrigin::Signed(seller), id, 0, ClaimOutcome::Replace)
+	verify {
+		assert_eq!(WarrantyClaims::<T>::get(id, 0).unwrap().outcome, Some(ClaimOutcome::Replace));
+	}
+
+	impl_benchmark_test_suite!(ProductModule, crate::mock::new_test_ext(), crate::mock::Test);
+}
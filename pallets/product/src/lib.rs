@@ -7,6 +7,12 @@ pub use pallet::*;
 
 pub mod types;
 
+pub mod traits;
+
+pub mod migrations;
+
+pub mod weights;
+
 #[cfg(test)]
 mod mock;
 
@@ -20,14 +26,22 @@ mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{pallet_prelude::*, traits::{Currency,  ExistenceRequirement}};
+	use frame_support::{pallet_prelude::*, PalletId, traits::{Currency, ReservableCurrency, ExistenceRequirement, BalanceStatus, fungibles::{self, Inspect as _, Transfer as _}}};
+	use sp_runtime::{traits::{AccountIdConversion, Saturating, UniqueSaturatedInto, Zero}, Perbill};
+	use sp_std::vec::Vec;
 	use frame_system::{pallet_prelude::*,};
-	use crate::types::{Product, ProductName, ProductPositionEnum, AccountIdOf, BalanceOf};
+	use crate::types::{Product, ProductName, ProductPositionEnum, PositionKind, AccountIdOf, BalanceOf, Role, Roles, ProductAction, ProductHistoryEntry, EscrowInfo, OfferInfo, AuctionInfo, AuctionKind, RecallInfo, RecallTarget, PaymentAsset, PayoutSplits, SaleInfo, ClaimInfo, ClaimOutcome, AssetIdOf, ProductMetadata, ProductInfo, is_valid_gtin};
+	use crate::traits::TransitionRules;
+	use crate::weights::WeightInfo;
 	use hex_literal::hex;
 
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -36,27 +50,274 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		type Currency: Currency<AccountIdOf<Self>>;
+		type Currency: ReservableCurrency<AccountIdOf<Self>>;
+
+		/// Identifier of the fungible assets products can be priced in.
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// Fungible assets used to settle products not priced in the native currency.
+		type Assets: fungibles::Transfer<Self::AccountId, AssetId = Self::AssetId, Balance = BalanceOf<Self>>;
+
+		/// The pallet account holding asset payments in escrow.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Deposit reserved from the creator for every stored product, returned by
+		/// `remove_product`.
+		#[pallet::constant]
+		type ProductDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum length of a product name in bytes.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+
+		/// Maximum length of a product description in bytes.
+		#[pallet::constant]
+		type MaxDescriptionLength: Get<u32>;
+
+		/// Allowed position changes for `update_position`.
+		type TransitionRules: TransitionRules;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Number of history entries kept per product, the oldest entry is dropped once full.
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+
+		/// Share of a resale price paid to the manufacturer of the product, the rest goes to the
+		/// reselling owner.
+		#[pallet::constant]
+		type ResaleRoyalty: Get<Perbill>;
+
+		/// Whether `buy_product` holds the price in escrow until the buyer confirms delivery.
+		#[pallet::constant]
+		type EscrowEnabled: Get<bool>;
+
+		/// Blocks after a purchase at which an open escrow is settled automatically, released
+		/// to the payee when shipped and refunded to the buyer otherwise.
+		#[pallet::constant]
+		type EscrowTimeout: Get<Self::BlockNumber>;
+
+		/// Number of escrows which can expire in the same block.
+		#[pallet::constant]
+		type MaxEscrowsPerBlock: Get<u32>;
+
+		/// Number of resale listings which can lapse in the same block.
+		#[pallet::constant]
+		type MaxResaleExpiriesPerBlock: Get<u32>;
+
+		/// Number of offers which can expire in the same block.
+		#[pallet::constant]
+		type MaxOffersPerBlock: Get<u32>;
+
+		/// Number of offers a product can hold at once.
+		#[pallet::constant]
+		type MaxOffersPerProduct: Get<u32>;
+
+		/// Number of auctions which can end in the same block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+
+		/// Origin administering authorized users and the product owner account, accounts with the
+		/// `Admin` role can manage users as well.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin allowed to recall any product, besides its manufacturer.
+		type RegulatorOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Number of products which can be recalled at once.
+		#[pallet::constant]
+		type MaxRecallBatch: Get<u32>;
+
+		/// Share of every sale price taken by the marketplace, paid to the product owner account.
+		#[pallet::constant]
+		type MarketplaceFee: Get<Perbill>;
+
+		/// Number of accounts sharing the first sale of a product.
+		#[pallet::constant]
+		type MaxSplits: Get<u32>;
+
+		/// Blocks after a purchase during which the buyer can ask to return the product.
+		#[pallet::constant]
+		type ReturnPeriod: Get<Self::BlockNumber>;
+
+		/// Blocks the seller has to answer a return request, an unanswered request is approved
+		/// automatically.
+		#[pallet::constant]
+		type ReturnResponsePeriod: Get<Self::BlockNumber>;
+
+		/// Number of return requests which can reach their response deadline in the same block.
+		#[pallet::constant]
+		type MaxReturnsPerBlock: Get<u32>;
+
+		/// Number of warranty claims which can be filed for a product.
+		#[pallet::constant]
+		type MaxClaimsPerProduct: Get<u32>;
 		
 	}
 
 
+	// roles held by every authorized account, an account without roles is not authorized
 	#[pallet::storage]
 	#[pallet::getter(fn get_authorized_user )]
-	pub(super) type AuthorizedUsers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool, 
+	pub(super) type AuthorizedUsers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Roles, 
     ValueQuery >;
 
 
+	// authorized users suspended until the given block
+	#[pallet::storage]
+	#[pallet::getter(fn get_suspension )]
+	pub(super) type SuspendedUsers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, 
+    OptionQuery >;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_product_info )]
-	pub(super) type Products<T:Config>= StorageMap<_, Blake2_128,u128,Product<T>, 
+	pub(super) type Products<T:Config>= StorageMap<_, Blake2_128Concat,u128,Product<T>, 
+    OptionQuery>;
+
+	// product ids by current owner, kept in sync with `Products`
+	#[pallet::storage]
+	pub(super) type ProductsByOwner<T:Config>= StorageDoubleMap<_, Blake2_128Concat,T::AccountId,
+	Blake2_128Concat,u128,(), OptionQuery>;
+
+	// product ids by the kind of their current position, kept in sync with `Products`
+	#[pallet::storage]
+	pub(super) type ProductsByPosition<T:Config>= StorageDoubleMap<_, Blake2_128Concat,PositionKind,
+	Blake2_128Concat,u128,(), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_product_metadata )]
+	pub(super) type ProductMetadataOf<T:Config>= StorageMap<_, Blake2_128Concat,u128,ProductMetadata<T>, 
+    OptionQuery>;
+
+	// depositor and amount reserved for every stored product
+	#[pallet::storage]
+	#[pallet::getter(fn get_product_deposit )]
+	pub(super) type ProductDeposits<T:Config>= StorageMap<_, Blake2_128Concat,u128,(T::AccountId, BalanceOf<T>), 
+    OptionQuery>;
+
+	// shares of the first sale paid to other accounts than the seller, set when the product is added
+	#[pallet::storage]
+	#[pallet::getter(fn get_product_splits )]
+	pub(super) type ProductSplits<T:Config>= StorageMap<_, Blake2_128Concat,u128,PayoutSplits<T>, 
+    OptionQuery>;
+
+	// chain of custody of every product, oldest entry first
+	#[pallet::storage]
+	#[pallet::getter(fn product_history )]
+	pub(super) type ProductHistory<T:Config>= StorageMap<_, Blake2_128Concat,u128,
+	BoundedVec<ProductHistoryEntry<T>, T::MaxHistoryLength>, ValueQuery>;
+
+	// open escrows by product id
+	#[pallet::storage]
+	#[pallet::getter(fn get_escrow )]
+	pub(super) type Escrows<T:Config>= StorageMap<_, Blake2_128Concat,u128,EscrowInfo<T>, 
+    OptionQuery>;
+
+	// products whose escrow is settled at the given block
+	#[pallet::storage]
+	pub(super) type EscrowDeadlines<T:Config>= StorageMap<_, Twox64Concat,T::BlockNumber,
+	BoundedVec<u128, T::MaxEscrowsPerBlock>, ValueQuery>;
+
+	// block at which the resale listing of a product lapses
+	#[pallet::storage]
+	#[pallet::getter(fn get_resale_expiry )]
+	pub(super) type ResaleExpiries<T:Config>= StorageMap<_, Blake2_128Concat,u128,T::BlockNumber, 
+    OptionQuery>;
+
+	// products whose resale listing lapses at the given block
+	#[pallet::storage]
+	pub(super) type ResaleDeadlines<T:Config>= StorageMap<_, Twox64Concat,T::BlockNumber,
+	BoundedVec<u128, T::MaxResaleExpiriesPerBlock>, ValueQuery>;
+
+	// recipient proposed by the owner, the product moves once the recipient accepts
+	#[pallet::storage]
+	#[pallet::getter(fn get_pending_transfer )]
+	pub(super) type PendingTransfers<T:Config>= StorageMap<_, Blake2_128Concat,u128,T::AccountId, 
+    OptionQuery>;
+
+	// open offers by product id and bidder, the offered amount is held from the bidder
+	#[pallet::storage]
+	#[pallet::getter(fn get_offer )]
+	pub(super) type Offers<T:Config>= StorageDoubleMap<_, Blake2_128Concat,u128,
+	Blake2_128Concat,T::AccountId,OfferInfo<T>, OptionQuery>;
+
+	// number of open offers by product id
+	#[pallet::storage]
+	#[pallet::getter(fn get_offer_count )]
+	pub(super) type OfferCounts<T:Config>= StorageMap<_, Blake2_128Concat,u128,u32, 
+    ValueQuery>;
+
+	// offers which expire at the given block
+	#[pallet::storage]
+	pub(super) type OfferDeadlines<T:Config>= StorageMap<_, Twox64Concat,T::BlockNumber,
+	BoundedVec<(u128, T::AccountId), T::MaxOffersPerBlock>, ValueQuery>;
+
+	// running auctions by product id
+	#[pallet::storage]
+	#[pallet::getter(fn get_auction )]
+	pub(super) type Auctions<T:Config>= StorageMap<_, Blake2_128Concat,u128,AuctionInfo<T>, 
+    OptionQuery>;
+
+	// auctions settled at the end of the given block
+	#[pallet::storage]
+	pub(super) type AuctionEnds<T:Config>= StorageMap<_, Twox64Concat,T::BlockNumber,
+	BoundedVec<u128, T::MaxAuctionsPerBlock>, ValueQuery>;
+
+	// unit price paid at the last native sale, the amount refunded on a recall
+	#[pallet::storage]
+	#[pallet::getter(fn get_sale_price )]
+	pub(super) type SalePrices<T:Config>= StorageMap<_, Blake2_128Concat,u128,BalanceOf<T>, 
+    OptionQuery>;
+
+	// payouts of the last sale of every product, paid back when a return is approved
+	#[pallet::storage]
+	#[pallet::getter(fn get_last_sale )]
+	pub(super) type LastSales<T:Config>= StorageMap<_, Blake2_128Concat,u128,SaleInfo<T>, 
+    OptionQuery>;
+
+	// block by which the seller has to answer the return the buyer asked for
+	#[pallet::storage]
+	#[pallet::getter(fn get_return_request )]
+	pub(super) type ReturnRequests<T:Config>= StorageMap<_, Blake2_128Concat,u128,T::BlockNumber, 
     OptionQuery>;
 
+	// return requests which are approved at the given block unless answered before
+	#[pallet::storage]
+	pub(super) type ReturnDeadlines<T:Config>= StorageMap<_, Twox64Concat,T::BlockNumber,
+	BoundedVec<u128, T::MaxReturnsPerBlock>, ValueQuery>;
+
+	// warranty claims of every product by claim number, resolved claims are kept as a record
+	#[pallet::storage]
+	#[pallet::getter(fn get_warranty_claim )]
+	pub(super) type WarrantyClaims<T:Config>= StorageDoubleMap<_, Blake2_128Concat,u128,
+	Twox64Concat,u32,ClaimInfo<T>, OptionQuery>;
+
+	// number of warranty claims filed per product, the next claim number
+	#[pallet::storage]
+	#[pallet::getter(fn get_claim_count )]
+	pub(super) type ClaimCounts<T:Config>= StorageMap<_, Blake2_128Concat,u128,u32, 
+    ValueQuery>;
+
+	// recalled products, which can no longer be sold
+	#[pallet::storage]
+	#[pallet::getter(fn get_recall )]
+	pub(super) type Recalls<T:Config>= StorageMap<_, Blake2_128Concat,u128,RecallInfo<T>, 
+    OptionQuery>;
+
+	// funds reserved by a manufacturer to refund the owners of its recalled products
+	#[pallet::storage]
+	#[pallet::getter(fn get_recall_deposit )]
+	pub(super) type RecallDeposits<T:Config>= StorageMap<_, Blake2_128Concat,T::AccountId,BalanceOf<T>, 
+    ValueQuery>;
+
 	//global counter for product
 	#[pallet::storage]
 	#[pallet::getter(fn get_product_counter )]	
 	pub(super) type ProductCounter<T> = StorageValue<_, u128, OptionQuery>;
 
+	// account receiving the marketplace fee of every sale
 	#[pallet::storage]
 	#[pallet::getter(fn get_product_owner_account)]
 	pub(super) type ProductOwnerAccount<T: Config> = StorageValue<_, AccountIdOf<T>, OptionQuery>;
@@ -69,9 +330,214 @@ pub mod pallet {
 		//even when new user is registered
 		NewAuthorizedUser(T::AccountId),
 
-		Product{
+		AuthorizedUserRemoved(T::AccountId),
+
+		AuthorizedUserSuspended{
+			who: T::AccountId,
+			until: T::BlockNumber,
+		},
+
+		RoleGranted{
+			who: T::AccountId,
+			role: Role,
+		},
+
+		RoleRevoked{
+			who: T::AccountId,
+			role: Role,
+		},
+
+		ProductCreated{
+			id: u128,
+			manufacturer: T::AccountId,
+			price: BalanceOf<T>,
+			quantity: u32,
+		},
+
+		PositionUpdated{
+			id: u128,
+			actor: T::AccountId,
+			from: ProductPositionEnum,
+			to: ProductPositionEnum,
+		},
+
+		ProductSold{
+			id: u128,
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			price: BalanceOf<T>,
+			quantity: u32,
+		},
+
+		ResaleEnabled{
+			id: u128,
+			owner: T::AccountId,
+			price: BalanceOf<T>,
+			expiry: Option<T::BlockNumber>,
+		},
+
+		ResaleDisabled{
+			id: u128,
+			owner: T::AccountId,
+		},
+
+		ResalePriceUpdated{
+			id: u128,
+			owner: T::AccountId,
+			price: BalanceOf<T>,
+		},
+
+		ResaleExpired{
+			id: u128,
+		},
+
+		ProductTransferred{
+			id: u128,
+			from: T::AccountId,
+			to: T::AccountId,
+		},
+
+		TransferProposed{
+			id: u128,
+			from: T::AccountId,
+			to: T::AccountId,
+		},
+
+		TransferCancelled{
+			id: u128,
+		},
+
+		OfferMade{
 			id: u128,
-			product:Product<T>,
+			bidder: T::AccountId,
+			amount: BalanceOf<T>,
+			expiry: T::BlockNumber,
+		},
+
+		OfferAccepted{
+			id: u128,
+			bidder: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+
+		OfferRejected{
+			id: u128,
+			bidder: T::AccountId,
+		},
+
+		OfferExpired{
+			id: u128,
+			bidder: T::AccountId,
+		},
+
+		AuctionStarted{
+			id: u128,
+			kind: AuctionKind,
+			reserve: BalanceOf<T>,
+			end: T::BlockNumber,
+		},
+
+		BidPlaced{
+			id: u128,
+			bidder: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+
+		// `winner` and the winning bid, none when the auction ended without a sale
+		AuctionSettled{
+			id: u128,
+			winner: Option<(T::AccountId, BalanceOf<T>)>,
+		},
+
+		ProductRecalled{
+			id: u128,
+			owner: T::AccountId,
+			reason_hash: T::Hash,
+		},
+
+		RecallRefunded{
+			id: u128,
+			owner: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+
+		RecallDepositChanged{
+			manufacturer: T::AccountId,
+			deposit: BalanceOf<T>,
+		},
+
+		ProductOwnerAccountSet{
+			account: T::AccountId,
+		},
+
+		ReturnRequested{
+			id: u128,
+			buyer: T::AccountId,
+			deadline: T::BlockNumber,
+		},
+
+		ReturnApproved{
+			id: u128,
+			buyer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+
+		ReturnRejected{
+			id: u128,
+			buyer: T::AccountId,
+		},
+
+		WarrantyClaimFiled{
+			id: u128,
+			claim: u32,
+			owner: T::AccountId,
+			evidence_hash: T::Hash,
+		},
+
+		WarrantyClaimResolved{
+			id: u128,
+			claim: u32,
+			outcome: ClaimOutcome,
+		},
+
+		BatchSplit{
+			id: u128,
+			new_id: u128,
+			quantity: u32,
+		},
+
+		MetadataUpdated{
+			id: u128,
+		},
+
+		ProductRemoved{
+			id: u128,
+		},
+
+		PriceAssetSet{
+			id: u128,
+			asset: PaymentAsset<AssetIdOf<T>>,
+		},
+
+		EscrowCreated{
+			id: u128,
+			buyer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+
+		Shipped{
+			id: u128,
+		},
+
+		EscrowReleased{
+			id: u128,
+			amount: BalanceOf<T>,
+		},
+
+		EscrowRefunded{
+			id: u128,
+			buyer: T::AccountId,
+			amount: BalanceOf<T>,
 		},
 	}
 
@@ -97,185 +563,1734 @@ pub mod pallet {
 
 		ProductIsNotSold,
 
-	}
+		MissingRole,
 
+		RoleAlreadyGranted,
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn add_authorized_user(origin: OriginFor<T>, new_user: T::AccountId) -> DispatchResult {
+		RoleNotGranted,
 
-			let is_root = Self::ensure_root_or_server(origin.clone()).is_ok();
-			if  !is_root {
-				Self::ensure_authorized(origin)?;
-			}
+		AuthorizedUserDonotExist,
 
-			ensure!(!AuthorizedUsers::<T>::contains_key(&new_user),Error::<T>::AuthorizedUserExist);
+		UserSuspended,
 
-			<AuthorizedUsers<T>>::insert(&new_user, true);
+		InvalidTransition,
 
-			Self::deposit_event(Event::NewAuthorizedUser(new_user));
-			Ok(())
-		}
+		ProductIsTerminal,
 
-		/// An example dispatchable that may throw a custom error.
-		#[pallet::call_index(1)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn add_product(origin: OriginFor<T>, name: ProductName, price: BalanceOf<T>) -> DispatchResult {
+		ProductInEscrow,
 
-			Self::ensure_authorized(origin.clone())?;
-			let sender = ensure_signed(origin)?;
-			let p = Product::<T>::new(name , price , sender, ProductPositionEnum::Manufacture);
+		EscrowDonotExist,
 
-			let product_counter = match Self::get_product_counter() {
-				Some(v)=> v+1,
-				None=> 1
-			} ;
+		NotSeller,
 
-			<ProductCounter<T>>::set(Some(product_counter));
-			<Products<T>>::insert(product_counter,&p);
-			
-			Self::deposit_event(Event::Product{
-				id: product_counter,
-				product: p,
-			});
-			Ok(())
+		NotBuyer,
 
-		}
-	
+		AlreadyShipped,
 
-		#[pallet::call_index(2)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn update_position(origin: OriginFor<T>,id: u128,  position: ProductPositionEnum)-> DispatchResult{
+		NotShipped,
 
-			Self::ensure_authorized(origin.clone())?;
+		TooManyEscrows,
 
-			ensure!(Products::<T>::contains_key(id),Error::<T>::ProductDonotExist);
+		InvalidQuantity,
 
-			let mut p:Product<T> =  Self::get_product_info(id).unwrap();
+		NotManufacturer,
 
-			ensure!(!p.get_is_sold(), Error::<T>::ProductIsSold);
-			
-			p.set_position(position);
-			<Products<T>>::insert(id,&p);
-			Self::deposit_event(Event::Product{
-				id:id,
-				product: p,
-			});
-			Ok(())
-		}
+		InvalidGtin,
 
-		#[pallet::call_index(3)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn buy_product(origin:OriginFor<T>, id: u128 )-> DispatchResult {
+		InvalidExpiry,
 
-			let buyer = ensure_signed(origin)?;
+		TooManyListings,
 
-			//check if product exists
-			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+		TransferToSelf,
 
+		NoPendingTransfer,
 
-			//product can be bought only after ready for retailer
-			ensure!(p.get_position() == 
-				ProductPositionEnum::Retailer, 
-				Error::<T>::NotReadyForRetailer
-			);
+		NotRecipient,
 
-			// sold but not in resale list
-			if p.get_is_sold() && !p.get_resale() {
-				return Err(Error::<T>::NotInResaleList.into());
-			}
+		OfferDonotExist,
 
-			// check sender balance greater than min balance
-			ensure!(
-				<T as Config>::Currency::free_balance(&buyer) >
-				p.get_price() + <T as Config>::Currency::minimum_balance(), 
-				Error::<T>::InsufficientBalance
-			);
+		OwnProduct,
 
-			// check if owner account exists
-			let owner_account  = Self::get_product_owner_account().
-					ok_or(Error::<T>::ServerAccountNotFound)?;
-			
-			
-			// transfer fund to product_owner_account
-			<T as Config>::Currency::transfer(&buyer, 
-							&owner_account, 
-							p.get_price(), 
-							ExistenceRequirement::AllowDeath
-			)?;
+		InvalidAmount,
 
-			//change product owner
-			p.set_owner(buyer);
+		TooManyOffers,
 
-			//set_sold_property
-			p.set_is_sold(true);
+		ProductInAuction,
 
-			//set_resale_false
-			p.set_resale(false);
+		AuctionDonotExist,
 
-			<Products<T>>::insert(id,&p);
-			Self::deposit_event(Event::Product{
-				id:id,
-				product: p,
-			});
+		AuctionEnded,
 
-			Ok(())
+		AuctionNotEnded,
+
+		BidTooLow,
+
+		InvalidDuration,
+
+		TooManyAuctions,
+
+		AuctionAssetNotNative,
+
+		ProductIsRecalled,
+
+		InsufficientRecallDeposit,
+
+		InvalidSplits,
+
+		NotReturnable,
+
+		ReturnPeriodOver,
+
+		ProductInReturn,
+
+		NoReturnRequest,
+
+		NoWarranty,
+
+		WarrantyExpired,
+
+		ClaimPending,
+
+		ClaimDonotExist,
+
+		ClaimResolved,
+
+		RecallNeedsRecallProduct,
+
+		TooManyClaims,
+
+		TooManyReturns,
+
+	}
+
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Registers a new account as a manufacturer, further roles are given with `grant_role`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::add_authorized_user())]
+		pub fn add_authorized_user(origin: OriginFor<T>, new_user: T::AccountId) -> DispatchResult {
+
+			Self::ensure_admin(origin)?;
+
+			ensure!(!AuthorizedUsers::<T>::contains_key(&new_user),Error::<T>::AuthorizedUserExist);
+
+			<AuthorizedUsers<T>>::insert(&new_user, Roles::from(Role::Manufacturer));
+
+			Self::deposit_event(Event::NewAuthorizedUser(new_user));
+			Ok(())
+		}
+
+		/// Adds a product, `splits` gives other accounts a share of its first sale after the
+		/// marketplace fee, the seller keeps the rest. A `warranty` lasts that many blocks after
+		/// the first sale.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::add_product())]
+		pub fn add_product(origin: OriginFor<T>, name: ProductName<T>, price: BalanceOf<T>, splits: Option<PayoutSplits<T>>, warranty: Option<T::BlockNumber>) -> DispatchResult {
+
+			Self::ensure_role(origin.clone(), Role::Manufacturer)?;
+			let sender = ensure_signed(origin)?;
+			Self::do_add_product(sender, name, price, 1, splits, warranty)
+		}
+
+		/// Adds a lot of `quantity` identical units sold at `unit_price` each.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::add_batch())]
+		pub fn add_batch(origin: OriginFor<T>, name: ProductName<T>, unit_price: BalanceOf<T>, quantity: u32, splits: Option<PayoutSplits<T>>, warranty: Option<T::BlockNumber>) -> DispatchResult {
+
+			Self::ensure_role(origin.clone(), Role::Manufacturer)?;
+			let sender = ensure_signed(origin)?;
+			ensure!(quantity > 0, Error::<T>::InvalidQuantity);
+			Self::do_add_product(sender, name, unit_price, quantity, splits, warranty)
+		}
+
+		/// Moves `quantity` units of a lot into a new product id with the same owner.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::split_batch())]
+		pub fn split_batch(origin: OriginFor<T>, id: u128, quantity: u32) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			ensure!(!Recalls::<T>::contains_key(id), Error::<T>::ProductIsRecalled);
+
+			Self::ensure_not_locked(id)?;
+
+			Self::do_split(id, &mut p, quantity, &owner)?;
+			Ok(())
+		}
+	
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::update_position())]
+		pub fn update_position(origin: OriginFor<T>,id: u128,  position: ProductPositionEnum)-> DispatchResult{
+
+			Self::ensure_role(origin.clone(), position.required_role())?;
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Products::<T>::contains_key(id),Error::<T>::ProductDonotExist);
+
+			let mut p:Product<T> =  Self::get_product_info(id).unwrap();
+
+			ensure!(!p.get_is_sold(), Error::<T>::ProductIsSold);
+
+			ensure!(!Escrows::<T>::contains_key(id), Error::<T>::ProductInEscrow);
+
+			// recalled and destroyed products can never move again
+			ensure!(!p.get_position().is_terminal(), Error::<T>::ProductIsTerminal);
+
+			// a recall is always recorded through `recall_product`
+			ensure!(position != ProductPositionEnum::Recalled, Error::<T>::RecallNeedsRecallProduct);
+
+			// only its own manufacturer can end the life of a product
+			if position.is_terminal() {
+				ensure!(sender == p.get_manufacturer(), Error::<T>::NotManufacturer);
+			}
+
+			ensure!(
+				T::TransitionRules::can_transition(p.get_position(), position),
+				Error::<T>::InvalidTransition
+			);
+			
+			let from = p.get_position();
+			p.set_position(position);
+			Self::put_product(id,&p);
+			Self::record_history(id, &sender, ProductAction::PositionUpdated, &p);
+			Self::deposit_event(Event::PositionUpdated{
+				id,
+				actor: sender,
+				from,
+				to: position,
+			});
+			Ok(())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::buy_product())]
+		pub fn buy_product(origin:OriginFor<T>, id: u128, quantity: u32)-> DispatchResult {
+
+			let buyer = ensure_signed(origin)?;
+
+			//check if product exists
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			ensure!(!Recalls::<T>::contains_key(id), Error::<T>::ProductIsRecalled);
+
+
+			//product can be bought only after ready for retailer
+			ensure!(p.get_position() == 
+				ProductPositionEnum::Retailer, 
+				Error::<T>::NotReadyForRetailer
+			);
+
+			// sold but not in resale list
+			if p.get_is_sold() && !p.get_resale() {
+				return Err(Error::<T>::NotInResaleList.into());
+			}
+
+			// a purchase is already waiting for delivery
+			ensure!(!Escrows::<T>::contains_key(id), Error::<T>::ProductInEscrow);
+
+			ensure!(quantity > 0 && quantity <= p.get_quantity(), Error::<T>::InvalidQuantity);
+
+			// check sender balance greater than min balance
+			let asset = p.get_asset();
+			ensure!(
+				Self::can_pay(asset, &buyer, p.get_price().saturating_mul(quantity.into())),
+				Error::<T>::InsufficientBalance
+			);
+
+			// buying part of a lot splits the bought units off into their own product, an escrowed
+			// lot can go back to the seller, who pays for its storage then
+			let (id, mut p) = if quantity < p.get_quantity() {
+				let depositor = if T::EscrowEnabled::get() { p.get_owner() } else { buyer.clone() };
+				Self::do_split(id, &mut p, quantity, &depositor)?
+			} else {
+				(id, p)
+			};
+
+			// fails early when the fee account is missing
+			let price = p.get_total_price();
+			let payouts = Self::sale_payouts(id, &mut p, price)?;
+
+			if T::EscrowEnabled::get() {
+				// hold the price until the buyer confirms delivery
+				let amount = price;
+				Self::hold(asset, &buyer, amount)?;
+
+				let deadline = <frame_system::Pallet<T>>::block_number() + T::EscrowTimeout::get();
+				<EscrowDeadlines<T>>::try_mutate(deadline, |ids| ids.try_push(id))
+					.map_err(|_| Error::<T>::TooManyEscrows)?;
+
+				<Escrows<T>>::insert(id, EscrowInfo::<T>{
+					buyer: buyer.clone(),
+					seller: p.get_owner(),
+					amount,
+					asset,
+					deadline,
+					shipped: false,
+				});
+				Self::deposit_event(Event::EscrowCreated{ id, buyer, amount });
+				return Ok(());
+			}
+
+			for (payee, amount) in payouts.iter() {
+				if amount.is_zero() {
+					continue;
+				}
+				Self::pay(asset, &buyer, payee, *amount)?;
+			}
+
+			Self::complete_sale(id, p, buyer, price, payouts);
+
+			Ok(())
 		}
 
 
+		/// Lists a sold product for resale at `price`, the listing lapses at the start of block
+		/// `expiry` when one is given.
 		#[pallet::call_index(4)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn enable_resale(origin:OriginFor<T>,id: u128, price: BalanceOf<T>)-> DispatchResult{
+		#[pallet::weight(T::WeightInfo::enable_resale())]
+		pub fn enable_resale(origin:OriginFor<T>,id: u128, price: BalanceOf<T>, expiry: Option<T::BlockNumber>)-> DispatchResult{
 			let owner =ensure_signed(origin)?;
 
 			// check if product exists
 			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
 
+			ensure!(!Recalls::<T>::contains_key(id), Error::<T>::ProductIsRecalled);
+
 			// validate owner
-			ensure!(owner== p.get_owner(), Error::<T>::BadOrigin);
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
 
 			// validate is sold
 			ensure!(p.get_is_sold(), Error::<T>::ProductIsNotSold);
 
+			Self::ensure_not_locked(id)?;
+
+			Self::set_resale_expiry(id, expiry)?;
+
 			// resale and price adjusted
 			p.set_resale(true);
 			p.set_price(price);
-			<Products<T>>::insert(id,&p);
-			Self::deposit_event(Event::Product{
-				id:id,
-				product: p,
+			Self::put_product(id,&p);
+			Self::record_history(id, &owner, ProductAction::ResaleEnabled, &p);
+			Self::deposit_event(Event::ResaleEnabled{
+				id,
+				owner,
+				price,
+				expiry,
 			});
 
 			Ok(())
 		}
-		
 
-	}
+		/// Withdraws the resale listing of a product, by the current owner.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::disable_resale())]
+		pub fn disable_resale(origin:OriginFor<T>, id: u128)-> DispatchResult{
+			let owner =ensure_signed(origin)?;
 
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
 
-	impl <T:Config> Pallet<T> {
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
 
-		// ensure root or is server account 	
-		pub fn ensure_root_or_server(origin: OriginFor<T>) -> DispatchResult {
-			let is_root = ensure_root(origin.clone()).is_ok();
-			let is_offchain = {
-				let signed = ensure_signed(origin);
-				signed.is_ok() && signed.ok() == Self::get_product_owner_account()
-			};
+			ensure!(p.get_resale(), Error::<T>::NotInResaleList);
+
+			ensure!(!Escrows::<T>::contains_key(id), Error::<T>::ProductInEscrow);
+
+			Self::clear_resale_expiry(id);
+			p.set_resale(false);
+			Self::put_product(id,&p);
+			Self::record_history(id, &owner, ProductAction::ResaleDisabled, &p);
+			Self::deposit_event(Event::ResaleDisabled{ id, owner });
 
-			ensure!(is_root || is_offchain, DispatchError::BadOrigin);
 			Ok(())
 		}
 
+		/// Changes the price of a listed product, by the current owner, the listing expiry is kept.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::update_resale_price())]
+		pub fn update_resale_price(origin:OriginFor<T>, id: u128, price: BalanceOf<T>)-> DispatchResult{
+			let owner =ensure_signed(origin)?;
 
-		pub fn ensure_authorized(origin: OriginFor<T>)-> DispatchResult{
-			let sender= ensure_signed(origin)?;
-			ensure!(AuthorizedUsers::<T>::contains_key(&sender) , DispatchError::BadOrigin);
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			ensure!(p.get_resale(), Error::<T>::NotInResaleList);
+
+			ensure!(!Escrows::<T>::contains_key(id), Error::<T>::ProductInEscrow);
+
+			p.set_price(price);
+			Self::put_product(id,&p);
+			Self::record_history(id, &owner, ProductAction::ResalePriceUpdated, &p);
+			Self::deposit_event(Event::ResalePriceUpdated{ id, owner, price });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::grant_role())]
+		pub fn grant_role(origin: OriginFor<T>, who: T::AccountId, role: Role) -> DispatchResult {
+
+			Self::ensure_admin(origin)?;
+
+			<AuthorizedUsers<T>>::try_mutate(&who, |roles| -> DispatchResult {
+				ensure!(!roles.contains(role), Error::<T>::RoleAlreadyGranted);
+				roles.insert(role);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::RoleGranted{ who, role });
+			Ok(())
+		}
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::revoke_role())]
+		pub fn revoke_role(origin: OriginFor<T>, who: T::AccountId, role: Role) -> DispatchResult {
+
+			Self::ensure_admin(origin)?;
+
+			<AuthorizedUsers<T>>::try_mutate_exists(&who, |maybe_roles| -> DispatchResult {
+				let roles = maybe_roles.as_mut().ok_or(Error::<T>::RoleNotGranted)?;
+				ensure!(roles.contains(role), Error::<T>::RoleNotGranted);
+				roles.remove(role);
+				// an account without any role is no longer authorized
+				if roles.is_empty() {
+					*maybe_roles = None;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::RoleRevoked{ who, role });
+			Ok(())
+		}
+
+		/// Removes an authorized user and any suspension, by `AdminOrigin` or an admin.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::remove_authorized_user())]
+		pub fn remove_authorized_user(origin: OriginFor<T>, user: T::AccountId) -> DispatchResult {
+
+			Self::ensure_admin(origin)?;
+
+			ensure!(AuthorizedUsers::<T>::contains_key(&user),Error::<T>::AuthorizedUserDonotExist);
+
+			<AuthorizedUsers<T>>::remove(&user);
+			<SuspendedUsers<T>>::remove(&user);
+
+			Self::deposit_event(Event::AuthorizedUserRemoved(user));
+			Ok(())
+		}
+
+		/// Suspends an authorized user, by `AdminOrigin` or an admin. The account is rejected by
+		/// `ensure_authorized` until `until_block` is reached.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::suspend_authorized_user())]
+		pub fn suspend_authorized_user(origin: OriginFor<T>, user: T::AccountId, until_block: T::BlockNumber) -> DispatchResult {
+
+			Self::ensure_admin(origin)?;
+
+			ensure!(AuthorizedUsers::<T>::contains_key(&user),Error::<T>::AuthorizedUserDonotExist);
+
+			<SuspendedUsers<T>>::insert(&user, until_block);
+
+			Self::deposit_event(Event::AuthorizedUserSuspended{
+				who: user,
+				until: until_block,
+			});
+			Ok(())
+		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::mark_shipped())]
+		pub fn mark_shipped(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			<Escrows<T>>::try_mutate(id, |maybe_escrow| -> DispatchResult {
+				let escrow = maybe_escrow.as_mut().ok_or(Error::<T>::EscrowDonotExist)?;
+				ensure!(sender == escrow.seller, Error::<T>::NotSeller);
+				ensure!(!escrow.shipped, Error::<T>::AlreadyShipped);
+				escrow.shipped = true;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Shipped{ id });
+			Ok(())
+		}
+
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::confirm_delivery())]
+		pub fn confirm_delivery(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let escrow = Self::get_escrow(id).ok_or(Error::<T>::EscrowDonotExist)?;
+			ensure!(sender == escrow.buyer, Error::<T>::NotBuyer);
+			ensure!(escrow.shipped, Error::<T>::NotShipped);
+
+			Self::release_escrow(id, escrow)
+		}
+
+		/// Removes a product from storage, by the current owner, and returns the storage deposit
+		/// to the account which created it.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::remove_product())]
+		pub fn remove_product(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			Self::ensure_not_locked(id)?;
+
+			Self::take_product(id);
+			Self::clear_resale_expiry(id);
+			<PendingTransfers<T>>::remove(id);
+			<SalePrices<T>>::remove(id);
+			<ProductSplits<T>>::remove(id);
+			<LastSales<T>>::remove(id);
+			<Recalls<T>>::remove(id);
+			let _ = <WarrantyClaims<T>>::clear_prefix(id, T::MaxClaimsPerProduct::get(), None);
+			<ClaimCounts<T>>::remove(id);
+			// give every open offer back to its bidder
+			for (bidder, offer) in <Offers<T>>::iter_prefix(id).collect::<Vec<_>>() {
+				Self::clear_offer(id, &bidder, &offer)?;
+			}
+			<ProductHistory<T>>::remove(id);
+			<ProductMetadataOf<T>>::remove(id);
+			if let Some((depositor, deposit)) = <ProductDeposits<T>>::take(id) {
+				<T as Config>::Currency::unreserve(&depositor, deposit);
+			}
+
+			Self::deposit_event(Event::ProductRemoved{ id });
+			Ok(())
+		}
+
+		/// Replaces the metadata of a product, by the manufacturer which added it.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::update_metadata())]
+		pub fn update_metadata(origin: OriginFor<T>, id: u128, metadata: ProductMetadata<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			ensure!(sender == p.get_manufacturer(), Error::<T>::NotManufacturer);
+
+			if let Some(gtin) = &metadata.gtin {
+				ensure!(is_valid_gtin(gtin), Error::<T>::InvalidGtin);
+			}
+
+			<ProductMetadataOf<T>>::insert(id, metadata);
+
+			Self::deposit_event(Event::MetadataUpdated{ id });
+			Ok(())
+		}
+
+		/// Hands a sold product to `to` without payment, by the current owner, any resale listing
+		/// is withdrawn.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::transfer_product())]
+		pub fn transfer_product(origin: OriginFor<T>, id: u128, to: T::AccountId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let p:Product<T> =  Self::ensure_transferable(id, &owner, &to)?;
+
+			Self::do_transfer(id, p, to);
+			Ok(())
+		}
+
+		/// Proposes `to` as the new owner of a sold product, the product moves once `to` calls
+		/// `accept_transfer`. A new proposal replaces the pending one.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::propose_transfer())]
+		pub fn propose_transfer(origin: OriginFor<T>, id: u128, to: T::AccountId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			Self::ensure_transferable(id, &owner, &to)?;
+
+			<PendingTransfers<T>>::insert(id, &to);
+
+			Self::deposit_event(Event::TransferProposed{ id, from: owner, to });
+			Ok(())
+		}
+
+		/// Accepts a transfer proposed with `propose_transfer`, by the proposed recipient.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::accept_transfer())]
+		pub fn accept_transfer(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let to = Self::get_pending_transfer(id).ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(sender == to, Error::<T>::NotRecipient);
+
+			let p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			Self::ensure_not_locked(id)?;
+
+			Self::do_transfer(id, p, to);
+			Ok(())
+		}
+
+		/// Withdraws a pending transfer proposal, by the current owner.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::cancel_transfer())]
+		pub fn cancel_transfer(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			ensure!(PendingTransfers::<T>::contains_key(id), Error::<T>::NoPendingTransfer);
+
+			<PendingTransfers<T>>::remove(id);
+
+			Self::deposit_event(Event::TransferCancelled{ id });
+			Ok(())
+		}
+
+		/// Offers `amount` for a sold product, held from the bidder until the owner accepts or
+		/// rejects the offer or it expires at block `expiry`. A new offer replaces the bidder's
+		/// previous one.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::make_offer())]
+		pub fn make_offer(origin: OriginFor<T>, id: u128, amount: BalanceOf<T>, expiry: T::BlockNumber) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			ensure!(!Recalls::<T>::contains_key(id), Error::<T>::ProductIsRecalled);
+
+			ensure!(bidder != p.get_owner(), Error::<T>::OwnProduct);
+
+			// unsold goods are bought from the supply chain at their price
+			ensure!(p.get_is_sold(), Error::<T>::ProductIsNotSold);
+
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+
+			ensure!(expiry > <frame_system::Pallet<T>>::block_number(), Error::<T>::InvalidExpiry);
+
+			if let Some(offer) = Self::get_offer(id, &bidder) {
+				Self::clear_offer(id, &bidder, &offer)?;
+			}
+
+			// keeps the offers given back on `remove_product` bounded
+			ensure!(Self::get_offer_count(id) < T::MaxOffersPerProduct::get(), Error::<T>::TooManyOffers);
+
+			let asset = p.get_asset();
+			ensure!(Self::can_pay(asset, &bidder, amount), Error::<T>::InsufficientBalance);
+			Self::hold(asset, &bidder, amount)?;
+
+			<OfferDeadlines<T>>::try_mutate(expiry, |offers| offers.try_push((id, bidder.clone())))
+				.map_err(|_| Error::<T>::TooManyOffers)?;
+			<Offers<T>>::insert(id, &bidder, OfferInfo::<T>{ amount, asset, expiry });
+			<OfferCounts<T>>::mutate(id, |n| *n = n.saturating_add(1));
+
+			Self::deposit_event(Event::OfferMade{ id, bidder, amount, expiry });
 			Ok(())
 		}
 
+		/// Sells the product to `bidder` for the held offer, by the current owner. The
+		/// manufacturer royalty is paid as for any resale.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::accept_offer())]
+		pub fn accept_offer(origin: OriginFor<T>, id: u128, bidder: T::AccountId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			ensure!(!Recalls::<T>::contains_key(id), Error::<T>::ProductIsRecalled);
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			Self::ensure_not_locked(id)?;
+
+			let offer = Self::get_offer(id, &bidder).ok_or(Error::<T>::OfferDonotExist)?;
+
+			let payouts = Self::sale_payouts(id, &mut p, offer.amount)?;
+			for (payee, amount) in payouts.iter() {
+				if amount.is_zero() {
+					continue;
+				}
+				Self::pay_held(offer.asset, &bidder, payee, *amount)?;
+			}
+
+			<Offers<T>>::remove(id, &bidder);
+			Self::drop_offer_count(id);
+			<OfferDeadlines<T>>::mutate(offer.expiry, |offers| offers.retain(|o| *o != (id, bidder.clone())));
+
+			Self::complete_sale(id, p, bidder.clone(), offer.amount, payouts);
+			Self::deposit_event(Event::OfferAccepted{ id, bidder, amount: offer.amount });
+			Ok(())
+		}
+
+		/// Declines an offer and returns the held amount to the bidder, by the current owner.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::reject_offer())]
+		pub fn reject_offer(origin: OriginFor<T>, id: u128, bidder: T::AccountId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			let offer = Self::get_offer(id, &bidder).ok_or(Error::<T>::OfferDonotExist)?;
+			Self::clear_offer(id, &bidder, &offer)?;
+
+			Self::deposit_event(Event::OfferRejected{ id, bidder });
+			Ok(())
+		}
+
+		/// Auctions a sold product, by the current owner, any resale listing is withdrawn. An
+		/// English auction sells to the highest bid of at least `reserve` once `duration` blocks
+		/// passed, a Dutch auction sells to the first bid at its price, which falls from the
+		/// product price to `reserve` over `duration` blocks.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::start_auction())]
+		pub fn start_auction(origin: OriginFor<T>, id: u128, kind: AuctionKind, reserve: BalanceOf<T>, duration: T::BlockNumber) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			ensure!(!Recalls::<T>::contains_key(id), Error::<T>::ProductIsRecalled);
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			ensure!(p.get_is_sold(), Error::<T>::ProductIsNotSold);
+
+			Self::ensure_not_locked(id)?;
+
+			// bids are reserved with `Config::Currency`
+			ensure!(p.get_asset() == PaymentAsset::Native, Error::<T>::AuctionAssetNotNative);
+
+			ensure!(!duration.is_zero(), Error::<T>::InvalidDuration);
+
+			let start = <frame_system::Pallet<T>>::block_number();
+			let end = start.saturating_add(duration);
+			<AuctionEnds<T>>::try_mutate(end, |ids| ids.try_push(id))
+				.map_err(|_| Error::<T>::TooManyAuctions)?;
+
+			<Auctions<T>>::insert(id, AuctionInfo::<T>{
+				kind,
+				reserve,
+				start_price: p.get_total_price().max(reserve),
+				start,
+				end,
+				best_bid: None,
+			});
+
+			if p.get_resale() {
+				p.set_resale(false);
+				Self::clear_resale_expiry(id);
+				Self::put_product(id,&p);
+				Self::record_history(id, &owner, ProductAction::ResaleDisabled, &p);
+			}
+
+			Self::deposit_event(Event::AuctionStarted{ id, kind, reserve, end });
+			Ok(())
+		}
+
+		/// Bids on a running auction. An English bid is reserved until it is outbid or the
+		/// auction settles, a Dutch bid at or above the current price buys the product at that
+		/// price right away.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::bid_english().max(T::WeightInfo::bid_dutch()))]
+		pub fn bid(origin: OriginFor<T>, id: u128, amount: BalanceOf<T>) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+
+			let mut auction = Self::get_auction(id).ok_or(Error::<T>::AuctionDonotExist)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			ensure!(bidder != p.get_owner(), Error::<T>::OwnProduct);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < auction.end, Error::<T>::AuctionEnded);
+
+			match auction.kind {
+				AuctionKind::English => {
+					ensure!(amount >= auction.reserve, Error::<T>::BidTooLow);
+					if let Some((_, best)) = &auction.best_bid {
+						ensure!(amount > *best, Error::<T>::BidTooLow);
+					}
+
+					// return the outbid reserve before taking the new one, the bidder may be
+					// raising its own bid
+					if let Some((outbid, best)) = auction.best_bid.take() {
+						<T as Config>::Currency::unreserve(&outbid, best);
+					}
+					ensure!(
+						Self::can_pay(PaymentAsset::Native, &bidder, amount),
+						Error::<T>::InsufficientBalance
+					);
+					<T as Config>::Currency::reserve(&bidder, amount)?;
+
+					auction.best_bid = Some((bidder.clone(), amount));
+					<Auctions<T>>::insert(id, auction);
+					Self::deposit_event(Event::BidPlaced{ id, bidder, amount });
+				},
+				AuctionKind::Dutch => {
+					let price = Self::dutch_price(&auction, now);
+					ensure!(amount >= price, Error::<T>::BidTooLow);
+					ensure!(
+						Self::can_pay(PaymentAsset::Native, &bidder, price),
+						Error::<T>::InsufficientBalance
+					);
+
+					let payouts = Self::sale_payouts(id, &mut p, price)?;
+					for (payee, share) in payouts.iter() {
+						if share.is_zero() {
+							continue;
+						}
+						Self::pay(PaymentAsset::Native, &bidder, payee, *share)?;
+					}
+
+					Self::clear_auction(id, &auction);
+					Self::deposit_event(Event::BidPlaced{ id, bidder: bidder.clone(), amount: price });
+					Self::complete_sale(id, p, bidder.clone(), price, payouts);
+					Self::deposit_event(Event::AuctionSettled{ id, winner: Some((bidder, price)) });
+				},
+			}
+			Ok(())
+		}
+
+		/// Settles an auction which reached its end, callable by anyone.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::settle_auction())]
+		pub fn settle_auction(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let auction = Self::get_auction(id).ok_or(Error::<T>::AuctionDonotExist)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= auction.end,
+				Error::<T>::AuctionNotEnded
+			);
+
+			Self::do_settle_auction(id, auction)
+		}
+
+		/// Recalls a product or a batch of products, by their manufacturer or `RegulatorOrigin`.
+		/// Recalled products can no longer be sold, escrowed purchases are refunded and running
+		/// auctions cancelled. With `refund` the current owners get the price paid at the last
+		/// native sale back out of the manufacturer's recall deposit, as far as it reaches.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::recall_product(target.count()))]
+		pub fn recall_product(origin: OriginFor<T>, target: RecallTarget<T>, reason_hash: T::Hash, refund: bool) -> DispatchResult {
+			// `None` for the regulator, which may recall any product
+			let manufacturer = match T::RegulatorOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			for id in target.ids() {
+				let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+				if let Some(who) = &manufacturer {
+					ensure!(*who == p.get_manufacturer(), Error::<T>::NotManufacturer);
+				}
+
+				ensure!(!Recalls::<T>::contains_key(id), Error::<T>::ProductIsRecalled);
+
+				Self::do_recall(id, p, reason_hash, refund)?;
+			}
+			Ok(())
+		}
+
+		/// Reserves `amount` more from the manufacturer for refunds of its recalled products.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::reserve_recall_deposit())]
+		pub fn reserve_recall_deposit(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			Self::ensure_role(origin.clone(), Role::Manufacturer)?;
+			let manufacturer = ensure_signed(origin)?;
+
+			<T as Config>::Currency::reserve(&manufacturer, amount)?;
+			let deposit = <RecallDeposits<T>>::mutate(&manufacturer, |deposit| {
+				*deposit = deposit.saturating_add(amount);
+				*deposit
+			});
+
+			Self::deposit_event(Event::RecallDepositChanged{ manufacturer, deposit });
+			Ok(())
+		}
+
+		/// Returns `amount` of the recall deposit to the manufacturer.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::release_recall_deposit())]
+		pub fn release_recall_deposit(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let manufacturer = ensure_signed(origin)?;
+
+			let deposit = Self::get_recall_deposit(&manufacturer);
+			ensure!(deposit >= amount, Error::<T>::InsufficientRecallDeposit);
+			let deposit = deposit - amount;
+			Self::set_recall_deposit(&manufacturer, deposit);
+			<T as Config>::Currency::unreserve(&manufacturer, amount);
+
+			Self::deposit_event(Event::RecallDepositChanged{ manufacturer, deposit });
+			Ok(())
+		}
+
+		/// Replaces the account receiving the `MarketplaceFee` of every sale, by `AdminOrigin`.
+		/// The rest of a first sale goes to the payout splits of the product and its seller.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::set_product_owner_account())]
+		pub fn set_product_owner_account(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<ProductOwnerAccount<T>>::put(&account);
+
+			Self::deposit_event(Event::ProductOwnerAccountSet{ account });
+			Ok(())
+		}
+
+		/// Asks the seller to take back a product bought within the last `ReturnPeriod` blocks,
+		/// withdrawing any resale listing. The return is approved if the seller does not answer
+		/// within `ReturnResponsePeriod` blocks.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::request_return())]
+		pub fn request_return(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(buyer == p.get_owner(), Error::<T>::BadOrigin);
+
+			let sale = Self::get_last_sale(id).ok_or(Error::<T>::NotReturnable)?;
+			ensure!(sale.buyer == buyer, Error::<T>::NotReturnable);
+
+			let purchased_at = p.get_purchased_at().ok_or(Error::<T>::NotReturnable)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= purchased_at + T::ReturnPeriod::get(), Error::<T>::ReturnPeriodOver);
+
+			ensure!(!Recalls::<T>::contains_key(id), Error::<T>::ProductIsRecalled);
+
+			Self::ensure_not_locked(id)?;
+
+			let deadline = now + T::ReturnResponsePeriod::get();
+			<ReturnDeadlines<T>>::try_mutate(deadline, |ids| ids.try_push(id))
+				.map_err(|_| Error::<T>::TooManyReturns)?;
+
+			if p.get_resale() {
+				p.set_resale(false);
+				Self::clear_resale_expiry(id);
+				Self::put_product(id,&p);
+				Self::record_history(id, &buyer, ProductAction::ResaleDisabled, &p);
+			}
+			<PendingTransfers<T>>::remove(id);
+
+			<ReturnRequests<T>>::insert(id, deadline);
+			Self::deposit_event(Event::ReturnRequested{ id, buyer, deadline });
+			Ok(())
+		}
+
+		/// Takes back a product the buyer asked to return, every payout of the sale is paid back
+		/// to the buyer.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::approve_return())]
+		pub fn approve_return(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(ReturnRequests::<T>::contains_key(id), Error::<T>::NoReturnRequest);
+			let sale = Self::get_last_sale(id).ok_or(Error::<T>::NoReturnRequest)?;
+			ensure!(seller == sale.seller, Error::<T>::NotSeller);
+
+			Self::do_return(id, sale)
+		}
+
+		/// Refuses to take back a product, the sale can not be returned any more.
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::reject_return())]
+		pub fn reject_return(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(ReturnRequests::<T>::contains_key(id), Error::<T>::NoReturnRequest);
+			let sale = Self::get_last_sale(id).ok_or(Error::<T>::NoReturnRequest)?;
+			ensure!(seller == sale.seller, Error::<T>::NotSeller);
+
+			Self::clear_return(id);
+			<LastSales<T>>::remove(id);
+			Self::deposit_event(Event::ReturnRejected{ id, buyer: sale.buyer });
+			Ok(())
+		}
+
+		/// Files a warranty claim for a product, by its owner while the warranty lasts. One claim
+		/// per product is open at a time.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::file_warranty_claim())]
+		pub fn file_warranty_claim(origin: OriginFor<T>, id: u128, evidence_hash: T::Hash) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			let expiry = p.get_warranty_expiry().ok_or(Error::<T>::NoWarranty)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= expiry, Error::<T>::WarrantyExpired);
+
+			let claim = Self::get_claim_count(id);
+			if let Some(last) = claim.checked_sub(1).and_then(|last| Self::get_warranty_claim(id, last)) {
+				ensure!(last.outcome.is_some(), Error::<T>::ClaimPending);
+			}
+			// keeps the claims cleared on `remove_product` bounded
+			ensure!(claim < T::MaxClaimsPerProduct::get(), Error::<T>::TooManyClaims);
+
+			<WarrantyClaims<T>>::insert(id, claim, ClaimInfo::<T>{
+				claimant: owner.clone(),
+				evidence_hash,
+				filed_at: now,
+				outcome: None,
+			});
+			<ClaimCounts<T>>::insert(id, claim + 1);
+
+			Self::record_history(id, &owner, ProductAction::WarrantyClaimed, &p);
+			Self::deposit_event(Event::WarrantyClaimFiled{ id, claim, owner, evidence_hash });
+			Ok(())
+		}
+
+		/// Records how the manufacturer of a product settled one of its warranty claims.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::resolve_claim())]
+		pub fn resolve_claim(origin: OriginFor<T>, id: u128, claim: u32, outcome: ClaimOutcome) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			ensure!(sender == p.get_manufacturer(), Error::<T>::NotManufacturer);
+
+			<WarrantyClaims<T>>::try_mutate(id, claim, |info| -> DispatchResult {
+				let info = info.as_mut().ok_or(Error::<T>::ClaimDonotExist)?;
+				ensure!(info.outcome.is_none(), Error::<T>::ClaimResolved);
+				info.outcome = Some(outcome);
+				Ok(())
+			})?;
+
+			Self::record_history(id, &sender, ProductAction::ClaimResolved, &p);
+			Self::deposit_event(Event::WarrantyClaimResolved{ id, claim, outcome });
+			Ok(())
+		}
+
+		/// Sets the asset the product price is denominated in, by the current owner.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_price_asset())]
+		pub fn set_price_asset(origin: OriginFor<T>, id: u128, asset: PaymentAsset<AssetIdOf<T>>) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			Self::ensure_not_locked(id)?;
+
+			p.set_asset(asset);
+			Self::put_product(id,&p);
+
+			Self::deposit_event(Event::PriceAssetSet{ id, asset });
+			Ok(())
+		}
+
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		// every stored product decodes with the current layout
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: T::BlockNumber) -> Result<(), &'static str> {
+			// a v1 migration spanning several blocks leaves the version behind until it is done
+			if crate::migrations::v1::MigrationCursor::<T>::exists() {
+				return Ok(());
+			}
+			ensure!(
+				Self::on_chain_storage_version() == STORAGE_VERSION,
+				"product storage version mismatch"
+			);
+			let stored = <Products<T>>::iter_keys().count();
+			let decoded = <Products<T>>::iter_values().count();
+			ensure!(stored == decoded, "undecodable product found");
+			Ok(())
+		}
+
+		// a zero period would schedule its expiry for the block already being built, where it is never taken
+		fn integrity_test() {
+			assert!(!T::EscrowTimeout::get().is_zero(), "`EscrowTimeout` must be at least one block");
+			assert!(!T::ReturnResponsePeriod::get().is_zero(), "`ReturnResponsePeriod` must be at least one block");
+		}
+
+		// settle every escrow which reached its deadline
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			// products still waiting under their pre-v1 keys are moved first
+			let migrating = crate::migrations::resume::<T>();
+
+			let ids = <EscrowDeadlines<T>>::take(n);
+			let count = ids.len() as u64;
+
+			for id in ids {
+				if let Some(escrow) = Self::get_escrow(id) {
+					// a failed settlement leaves the escrow untouched instead of half settled
+					let _ = frame_support::storage::with_storage_layer(|| if escrow.shipped {
+						Self::release_escrow(id, escrow)
+					} else {
+						Self::refund_escrow(id, escrow)
+					});
+				}
+			}
+
+			// withdraw every resale listing which reached its expiry
+			let listings = <ResaleDeadlines<T>>::take(n);
+			let expired = listings.len() as u64;
+
+			for id in listings {
+				<ResaleExpiries<T>>::remove(id);
+				Self::expire_listing(id);
+			}
+
+			// return the held amount of every offer which reached its expiry
+			let offers = <OfferDeadlines<T>>::take(n);
+			let lapsed = offers.len() as u64;
+
+			for (id, bidder) in offers {
+				if let Some(offer) = <Offers<T>>::take(id, &bidder) {
+					Self::drop_offer_count(id);
+					let _ = Self::release_held(offer.asset, &bidder, offer.amount);
+					Self::deposit_event(Event::OfferExpired{ id, bidder });
+				}
+			}
+
+			// approve every return the seller left unanswered
+			let returns = <ReturnDeadlines<T>>::take(n);
+			let unanswered = returns.len() as u64;
+
+			for id in returns {
+				let returned = Self::get_last_sale(id).map_or(false, |sale| {
+					frame_support::storage::with_storage_layer(|| Self::do_return(id, sale)).is_ok()
+				});
+				// a return the seller can not pay back leaves the product with the buyer
+				if !returned {
+					<ReturnRequests<T>>::remove(id);
+				}
+			}
+
+			// auctions ending in this block are settled in `on_finalize`
+			let ending = <AuctionEnds<T>>::decode_len(n).unwrap_or(0) as u64;
+
+			// a released escrow pays every payee of the sale, as `confirm_delivery` does
+			T::DbWeight::get().reads_writes(
+				5 + 2 * expired + 3 * lapsed,
+				4 + 6 * expired + 3 * lapsed,
+			).saturating_add(T::WeightInfo::confirm_delivery().saturating_mul(count))
+			.saturating_add(T::WeightInfo::approve_return().saturating_mul(unanswered))
+			.saturating_add(T::WeightInfo::settle_auction().saturating_mul(ending))
+			.saturating_add(migrating)
+		}
+
+		// settle every auction which reached its end
+		fn on_finalize(n: T::BlockNumber) {
+			for id in <AuctionEnds<T>>::take(n) {
+				if let Some(auction) = Self::get_auction(id) {
+					// a failed settlement leaves the auction for `settle_auction`
+					let _ = frame_support::storage::with_storage_layer(|| Self::do_settle_auction(id, auction));
+				}
+			}
+		}
+	}
+
+
+	impl <T:Config> Pallet<T> {
+
+		// hand the product to the buyer once `price` has been paid
+		pub fn complete_sale(id: u128, mut p: Product<T>, buyer: T::AccountId, price: BalanceOf<T>, payouts: Vec<(T::AccountId, BalanceOf<T>)>){
+			let seller = p.get_owner();
+
+			// keep what was paid to whom for a return within the return period
+			let payouts: Vec<_> = payouts.into_iter().filter(|(_, amount)| !amount.is_zero()).collect();
+			<LastSales<T>>::insert(id, SaleInfo::<T>{
+				buyer: buyer.clone(),
+				seller: seller.clone(),
+				asset: p.get_asset(),
+				// at most the fee, the royalty, every split and the seller
+				payouts: BoundedVec::try_from(payouts).unwrap_or_default(),
+				first_sale: !p.get_is_sold(),
+			});
+			let now = <frame_system::Pallet<T>>::block_number();
+			p.set_purchased_at(Some(now));
+
+			// the warranty runs from the first sale
+			if !p.get_is_sold() {
+				if let Some(period) = p.get_warranty_period() {
+					p.set_warranty_expiry(Some(now + period));
+				}
+			}
+
+			//change product owner
+			p.set_owner(buyer.clone());
+
+			//set_sold_property
+			p.set_is_sold(true);
+
+			//set_resale_false
+			p.set_resale(false);
+			Self::clear_resale_expiry(id);
+			<PendingTransfers<T>>::remove(id);
+
+			// remember what was paid per unit, refunds are only made from native deposits
+			if p.get_asset() == PaymentAsset::Native && p.get_quantity() > 0 {
+				<SalePrices<T>>::insert(id, price / p.get_quantity().into());
+			} else {
+				<SalePrices<T>>::remove(id);
+			}
+
+			Self::put_product(id,&p);
+			Self::record_history(id, &buyer, ProductAction::Sold, &p);
+			Self::deposit_event(Event::ProductSold{
+				id,
+				seller,
+				buyer,
+				price,
+				quantity: p.get_quantity(),
+			});
+		}
+
+		// a product in escrow, in auction or waiting for a return is locked to its current sale
+		fn ensure_not_locked(id: u128) -> DispatchResult {
+			ensure!(!Escrows::<T>::contains_key(id), Error::<T>::ProductInEscrow);
+			ensure!(!Auctions::<T>::contains_key(id), Error::<T>::ProductInAuction);
+			ensure!(!ReturnRequests::<T>::contains_key(id), Error::<T>::ProductInReturn);
+			Ok(())
+		}
+
+		// returns product `id` as long as `owner` may hand it to `to`
+		fn ensure_transferable(id: u128, owner: &T::AccountId, to: &T::AccountId) -> Result<Product<T>, DispatchError> {
+			let mut p:Product<T> =  Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			// validate owner
+			ensure!(*owner == p.get_owner(), Error::<T>::BadOrigin);
+
+			ensure!(owner != to, Error::<T>::TransferToSelf);
+
+			// goods still in the supply chain only change hands through a sale
+			ensure!(p.get_is_sold(), Error::<T>::ProductIsNotSold);
+
+			Self::ensure_not_locked(id)?;
+
+			Ok(p)
+		}
+
+		// move a product to `to` without payment, withdrawing any resale listing
+		pub fn do_transfer(id: u128, mut p: Product<T>, to: T::AccountId){
+			let from = p.get_owner();
+
+			p.set_owner(to.clone());
+			p.set_resale(false);
+			Self::clear_resale_expiry(id);
+			<PendingTransfers<T>>::remove(id);
+
+			Self::put_product(id,&p);
+			Self::record_history(id, &from, ProductAction::Transferred, &p);
+			Self::deposit_event(Event::ProductTransferred{ id, from, to });
+		}
+
+		// pay every payout of the last sale back to the buyer and hand the product back to the
+		// seller
+		pub fn do_return(id: u128, sale: SaleInfo<T>) -> DispatchResult {
+			let mut p = Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			let mut amount = BalanceOf::<T>::zero();
+			for (payee, share) in sale.payouts.iter() {
+				Self::pay(sale.asset, payee, &sale.buyer, *share)?;
+				amount = amount.saturating_add(*share);
+			}
+
+			p.set_owner(sale.seller.clone());
+			p.set_is_sold(!sale.first_sale);
+			p.set_resale(false);
+			p.set_purchased_at(None);
+			if sale.first_sale {
+				<SalePrices<T>>::remove(id);
+				p.set_warranty_expiry(None);
+			}
+			Self::put_product(id,&p);
+
+			Self::clear_return(id);
+			<LastSales<T>>::remove(id);
+			Self::record_history(id, &sale.buyer, ProductAction::Returned, &p);
+			Self::deposit_event(Event::ReturnApproved{ id, buyer: sale.buyer, amount });
+			Ok(())
+		}
+
+		pub fn do_add_product(sender: T::AccountId, name: ProductName<T>, price: BalanceOf<T>, quantity: u32, splits: Option<PayoutSplits<T>>, warranty: Option<T::BlockNumber>) -> DispatchResult {
+			// the shares can not give away more than the whole price
+			if let Some(splits) = &splits {
+				let total = splits.iter().fold(0u64, |total, (_, share)| total + share.deconstruct() as u64);
+				ensure!(total <= Perbill::one().deconstruct() as u64, Error::<T>::InvalidSplits);
+			}
+
+			let mut p = Product::<T>::new(name , price , sender.clone(), ProductPositionEnum::Manufacture);
+			p.set_quantity(quantity);
+			p.set_warranty_period(warranty);
+
+			let product_counter = Self::store_new_product(&p, &sender)?;
+			if let Some(splits) = splits {
+				<ProductSplits<T>>::insert(product_counter, splits);
+			}
+			Self::record_history(product_counter, &sender, ProductAction::Created, &p);
+			
+			Self::deposit_event(Event::ProductCreated{
+				id: product_counter,
+				manufacturer: sender,
+				price,
+				quantity,
+			});
+			Ok(())
+		}
+
+		// store a product under the next id, reserving the storage deposit from `depositor`
+		fn store_new_product(p: &Product<T>, depositor: &T::AccountId) -> Result<u128, DispatchError> {
+			// pay for the storage used by the product
+			let deposit = T::ProductDeposit::get();
+			<T as Config>::Currency::reserve(depositor, deposit)?;
+
+			let product_counter = match Self::get_product_counter() {
+				Some(v)=> v+1,
+				None=> 1
+			} ;
+
+			<ProductCounter<T>>::set(Some(product_counter));
+			Self::put_product(product_counter,p);
+			<ProductDeposits<T>>::insert(product_counter, (depositor.clone(), deposit));
+			Ok(product_counter)
+		}
+
+		// move `quantity` units of lot `id` into a new product, the deposit of the new entry is
+		// reserved from `depositor`
+		pub fn do_split(id: u128, p: &mut Product<T>, quantity: u32, depositor: &T::AccountId) -> Result<(u128, Product<T>), DispatchError> {
+			ensure!(quantity > 0 && quantity < p.get_quantity(), Error::<T>::InvalidQuantity);
+
+			let mut new_p = p.clone();
+			new_p.set_quantity(quantity);
+			p.set_quantity(p.get_quantity() - quantity);
+
+			let new_id = Self::store_new_product(&new_p, depositor)?;
+			Self::put_product(id, p);
+			// a lot is only returned as it was bought
+			<LastSales<T>>::remove(id);
+			if let Some(metadata) = Self::get_product_metadata(id) {
+				<ProductMetadataOf<T>>::insert(new_id, metadata);
+			}
+			if let Some(unit_price) = Self::get_sale_price(id) {
+				<SalePrices<T>>::insert(new_id, unit_price);
+			}
+			if let Some(splits) = Self::get_product_splits(id) {
+				<ProductSplits<T>>::insert(new_id, splits);
+			}
+			// every unit of a recalled lot stays out of trade
+			if let Some(recall) = Self::get_recall(id) {
+				<Recalls<T>>::insert(new_id, recall);
+			}
+
+			// the new lot carries the history of the lot it came from
+			<ProductHistory<T>>::insert(new_id, Self::product_history(id));
+			let actor = p.get_owner();
+			Self::record_history(id, &actor, ProductAction::Split, p);
+			Self::record_history(new_id, &actor, ProductAction::Split, &new_p);
+			Self::deposit_event(Event::BatchSplit{ id, new_id, quantity });
+			Ok((new_id, new_p))
+		}
+
+		// who receives how much of `price` when product `id` is sold, the marketplace fee goes to
+		// the product owner account and the seller keeps the rest, minus the product splits on a
+		// first sale and the manufacturer royalty on a resale
+		pub fn sale_payouts(id: u128, p: &mut Product<T>, price: BalanceOf<T>) -> Result<Vec<(T::AccountId, BalanceOf<T>)>, DispatchError> {
+			let mut payouts = Vec::new();
+
+			let fee = T::MarketplaceFee::get() * price;
+			if !fee.is_zero() {
+				// check if owner account exists
+				let owner_account  = Self::get_product_owner_account().
+						ok_or(Error::<T>::ServerAccountNotFound)?;
+				payouts.push((owner_account, fee));
+			}
+			let mut rest = price.saturating_sub(fee);
+
+			if !p.get_is_sold() {
+				let proceeds = rest;
+				for (payee, share) in Self::get_product_splits(id).unwrap_or_default() {
+					let amount = share * proceeds;
+					rest = rest.saturating_sub(amount);
+					payouts.push((payee, amount));
+				}
+			} else {
+				let royalty = T::ResaleRoyalty::get() * price;
+				rest = rest.saturating_sub(royalty);
+				payouts.push((p.get_manufacturer(), royalty));
+			}
+
+			payouts.push((p.get_owner(), rest));
+			Ok(payouts)
+		}
+
+		// pay the escrowed price to the payees and complete the sale
+		pub fn release_escrow(id: u128, escrow: EscrowInfo<T>) -> DispatchResult {
+			let mut p = Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+
+			let payouts = Self::sale_payouts(id, &mut p, escrow.amount)?;
+			for (payee, amount) in payouts.iter() {
+				if amount.is_zero() {
+					continue;
+				}
+				Self::pay_held(escrow.asset, &escrow.buyer, payee, *amount)?;
+			}
+
+			Self::clear_escrow(id, &escrow);
+			Self::complete_sale(id, p, escrow.buyer, escrow.amount, payouts);
+			Self::deposit_event(Event::EscrowReleased{
+				id,
+				amount: escrow.amount,
+			});
+			Ok(())
+		}
+
+		// give the escrowed price back to the buyer, the product stays with the seller
+		pub fn refund_escrow(id: u128, escrow: EscrowInfo<T>) -> DispatchResult {
+			Self::release_held(escrow.asset, &escrow.buyer, escrow.amount)?;
+
+			Self::clear_escrow(id, &escrow);
+			Self::deposit_event(Event::EscrowRefunded{
+				id,
+				buyer: escrow.buyer,
+				amount: escrow.amount,
+			});
+			Ok(())
+		}
+
+		// replace the expiry of the resale listing of `id`, no expiry keeps it listed until sold or
+		// withdrawn
+		fn set_resale_expiry(id: u128, expiry: Option<T::BlockNumber>) -> DispatchResult {
+			if let Some(expiry) = expiry {
+				ensure!(expiry > <frame_system::Pallet<T>>::block_number(), Error::<T>::InvalidExpiry);
+			}
+
+			Self::clear_resale_expiry(id);
+			if let Some(expiry) = expiry {
+				<ResaleDeadlines<T>>::try_mutate(expiry, |ids| ids.try_push(id))
+					.map_err(|_| Error::<T>::TooManyListings)?;
+				<ResaleExpiries<T>>::insert(id, expiry);
+			}
+			Ok(())
+		}
+
+		fn clear_resale_expiry(id: u128){
+			if let Some(expiry) = <ResaleExpiries<T>>::take(id) {
+				<ResaleDeadlines<T>>::mutate(expiry, |ids| ids.retain(|i| *i != id));
+			}
+		}
+
+		// take a product off the resale list once its listing lapsed
+		fn expire_listing(id: u128){
+			let mut p = match Self::get_product_info(id) {
+				Some(p) => p,
+				None => return,
+			};
+			if !p.get_resale() {
+				return;
+			}
+
+			p.set_resale(false);
+			Self::put_product(id,&p);
+			let owner = p.get_owner();
+			Self::record_history(id, &owner, ProductAction::ResaleExpired, &p);
+			Self::deposit_event(Event::ResaleExpired{ id });
+		}
+
+		// take a product out of trade for good, refunding the owner out of the manufacturer's
+		// recall deposit when asked to
+		fn do_recall(id: u128, mut p: Product<T>, reason_hash: T::Hash, refund: bool) -> DispatchResult {
+			if let Some(escrow) = Self::get_escrow(id) {
+				Self::refund_escrow(id, escrow)?;
+			}
+			if let Some(auction) = Self::get_auction(id) {
+				if let Some((bidder, amount)) = &auction.best_bid {
+					<T as Config>::Currency::unreserve(bidder, *amount);
+				}
+				Self::clear_auction(id, &auction);
+				Self::deposit_event(Event::AuctionSettled{ id, winner: None });
+			}
+
+			let owner = p.get_owner();
+			let manufacturer = p.get_manufacturer();
+			let mut refunded = BalanceOf::<T>::zero();
+			if refund && owner != manufacturer {
+				let paid = Self::get_sale_price(id).unwrap_or_default()
+					.saturating_mul(p.get_quantity().into());
+				let amount = paid.min(Self::get_recall_deposit(&manufacturer));
+				if !amount.is_zero() {
+					let remaining = <T as Config>::Currency::repatriate_reserved(
+						&manufacturer,
+						&owner,
+						amount,
+						BalanceStatus::Free,
+					)?;
+					refunded = amount - remaining;
+					Self::set_recall_deposit(&manufacturer, Self::get_recall_deposit(&manufacturer).saturating_sub(refunded));
+					Self::deposit_event(Event::RecallRefunded{ id, owner: owner.clone(), amount: refunded });
+				}
+			}
+
+			// a recall replaces any return
+			Self::clear_return(id);
+			<LastSales<T>>::remove(id);
+
+			p.set_resale(false);
+			Self::clear_resale_expiry(id);
+			if !p.get_position().is_terminal() {
+				p.set_position(ProductPositionEnum::Recalled);
+			}
+			Self::put_product(id,&p);
+
+			<Recalls<T>>::insert(id, RecallInfo::<T>{
+				reason_hash,
+				block_number: <frame_system::Pallet<T>>::block_number(),
+				refunded,
+			});
+			Self::record_history(id, &manufacturer, ProductAction::Recalled, &p);
+			Self::deposit_event(Event::ProductRecalled{ id, owner, reason_hash });
+			Ok(())
+		}
+
+		fn set_recall_deposit(manufacturer: &T::AccountId, deposit: BalanceOf<T>){
+			if deposit.is_zero() {
+				<RecallDeposits<T>>::remove(manufacturer);
+			} else {
+				<RecallDeposits<T>>::insert(manufacturer, deposit);
+			}
+		}
+
+		// price of a Dutch auction at block `now`, falling linearly from the start price to the
+		// reserve
+		pub fn dutch_price(auction: &AuctionInfo<T>, now: T::BlockNumber) -> BalanceOf<T> {
+			let elapsed: u32 = now.saturating_sub(auction.start).unique_saturated_into();
+			let duration: u32 = auction.end.saturating_sub(auction.start).unique_saturated_into();
+			let span = auction.start_price.saturating_sub(auction.reserve);
+			auction.start_price.saturating_sub(Perbill::from_rational(elapsed, duration) * span)
+		}
+
+		// sell to the best bid of an ended auction, an auction without bids ends without a sale
+		pub fn do_settle_auction(id: u128, auction: AuctionInfo<T>) -> DispatchResult {
+			Self::clear_auction(id, &auction);
+
+			let (winner, amount) = match auction.best_bid {
+				Some(best) => best,
+				None => {
+					Self::deposit_event(Event::AuctionSettled{ id, winner: None });
+					return Ok(());
+				}
+			};
+
+			let mut p = Self::get_product_info(id).ok_or(Error::<T>::ProductDonotExist)?;
+			let payouts = Self::sale_payouts(id, &mut p, amount)?;
+			for (payee, share) in payouts.iter() {
+				if share.is_zero() {
+					continue;
+				}
+				Self::pay_held(PaymentAsset::Native, &winner, payee, *share)?;
+			}
+
+			Self::complete_sale(id, p, winner.clone(), amount, payouts);
+			Self::deposit_event(Event::AuctionSettled{ id, winner: Some((winner, amount)) });
+			Ok(())
+		}
+
+		fn clear_auction(id: u128, auction: &AuctionInfo<T>){
+			<Auctions<T>>::remove(id);
+			<AuctionEnds<T>>::mutate(auction.end, |ids| ids.retain(|i| *i != id));
+		}
+
+		// drop an open offer and give the held amount back to the bidder
+		fn clear_offer(id: u128, bidder: &T::AccountId, offer: &OfferInfo<T>) -> DispatchResult {
+			Self::release_held(offer.asset, bidder, offer.amount)?;
+			<Offers<T>>::remove(id, bidder);
+			Self::drop_offer_count(id);
+			<OfferDeadlines<T>>::mutate(offer.expiry, |offers| offers.retain(|o| *o != (id, bidder.clone())));
+			Ok(())
+		}
+
+		fn drop_offer_count(id: u128){
+			<OfferCounts<T>>::mutate_exists(id, |n| *n = n.map(|n| n.saturating_sub(1)).filter(|n| *n > 0));
+		}
+
+		fn clear_escrow(id: u128, escrow: &EscrowInfo<T>){
+			<Escrows<T>>::remove(id);
+			<EscrowDeadlines<T>>::mutate(escrow.deadline, |ids| ids.retain(|i| *i != id));
+		}
+
+		fn clear_return(id: u128){
+			if let Some(deadline) = <ReturnRequests<T>>::take(id) {
+				<ReturnDeadlines<T>>::mutate(deadline, |ids| ids.retain(|i| *i != id));
+			}
+		}
+
+		// account holding asset payments in escrow
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		// whether `who` can pay `amount` of `asset` and keep its account alive
+		pub fn can_pay(asset: PaymentAsset<AssetIdOf<T>>, who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+			match asset {
+				PaymentAsset::Native=> <T as Config>::Currency::free_balance(who) >
+					amount + <T as Config>::Currency::minimum_balance(),
+				PaymentAsset::Asset(asset_id)=> T::Assets::reducible_balance(asset_id, who, true) >= amount,
+			}
+		}
+
+		pub fn pay(asset: PaymentAsset<AssetIdOf<T>>, from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			match asset {
+				PaymentAsset::Native=> <T as Config>::Currency::transfer(from, 
+								to, 
+								amount, 
+								ExistenceRequirement::AllowDeath
+				),
+				PaymentAsset::Asset(asset_id)=> T::Assets::transfer(asset_id, from, to, amount, false).map(|_| ()),
+			}
+		}
+
+		// native payments are reserved on the payer, assets move to the pallet account
+		pub fn hold(asset: PaymentAsset<AssetIdOf<T>>, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			match asset {
+				PaymentAsset::Native=> <T as Config>::Currency::reserve(who, amount),
+				PaymentAsset::Asset(asset_id)=> T::Assets::transfer(asset_id, who, &Self::account_id(), amount, false).map(|_| ()),
+			}
+		}
+
+		// pay out of funds held for `from` with `hold`
+		pub fn pay_held(asset: PaymentAsset<AssetIdOf<T>>, from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			match asset {
+				PaymentAsset::Native=> {
+					let remaining = <T as Config>::Currency::repatriate_reserved(
+						from,
+						to,
+						amount,
+						BalanceStatus::Free,
+					)?;
+					ensure!(remaining.is_zero(), Error::<T>::InsufficientBalance);
+					Ok(())
+				},
+				PaymentAsset::Asset(asset_id)=> T::Assets::transfer(asset_id, &Self::account_id(), to, amount, false).map(|_| ()),
+			}
+		}
+
+		// give funds held with `hold` back to `who`
+		pub fn release_held(asset: PaymentAsset<AssetIdOf<T>>, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			match asset {
+				PaymentAsset::Native=> {
+					<T as Config>::Currency::unreserve(who, amount);
+					Ok(())
+				},
+				PaymentAsset::Asset(asset_id)=> T::Assets::transfer(asset_id, &Self::account_id(), who, amount, false).map(|_| ()),
+			}
+		}
+
+
+		// product as served by the runtime api
+		pub fn product_info(id: u128) -> Option<ProductInfo<AccountIdOf<T>, BalanceOf<T>, AssetIdOf<T>>> {
+			Self::get_product_info(id).map(Into::into)
+		}
+
+		// ids of the products owned by `owner`, ascending
+		pub fn products_by_owner(owner: &T::AccountId) -> Vec<u128> {
+			let mut ids: Vec<u128> = <ProductsByOwner<T>>::iter_key_prefix(owner).collect();
+			ids.sort();
+			ids
+		}
+
+		// ids of the products currently at a position of `kind`, ascending
+		pub fn products_by_position(kind: PositionKind) -> Vec<u128> {
+			let mut ids: Vec<u128> = <ProductsByPosition<T>>::iter_key_prefix(kind).collect();
+			ids.sort();
+			ids
+		}
+
+		// every write to `Products` goes through here so the indexes follow owner and position
+		pub(crate) fn put_product(id: u128, p: &Product<T>){
+			if let Some(old) = Self::get_product_info(id) {
+				let (owner, position) = old.index_keys();
+				<ProductsByOwner<T>>::remove(owner, id);
+				<ProductsByPosition<T>>::remove(position, id);
+			}
+
+			let (owner, position) = p.index_keys();
+			<ProductsByOwner<T>>::insert(owner, id, ());
+			<ProductsByPosition<T>>::insert(position, id, ());
+			<Products<T>>::insert(id, p);
+		}
+
+		pub(crate) fn take_product(id: u128) -> Option<Product<T>> {
+			let p = <Products<T>>::take(id)?;
+			let (owner, position) = p.index_keys();
+			<ProductsByOwner<T>>::remove(owner, id);
+			<ProductsByPosition<T>>::remove(position, id);
+			Some(p)
+		}
+
+
+		pub fn ensure_authorized(origin: OriginFor<T>)-> DispatchResult{
+			let sender= ensure_signed(origin)?;
+			ensure!(AuthorizedUsers::<T>::contains_key(&sender) , DispatchError::BadOrigin);
+			ensure!(!Self::is_suspended(&sender), Error::<T>::UserSuspended);
+			Ok(())
+		}
+
+		// append to the product history, dropping the oldest entry when the bound is reached
+		pub fn record_history(id: u128, actor: &T::AccountId, action: ProductAction, product: &Product<T>){
+			let entry = ProductHistoryEntry::<T>::new(
+				<frame_system::Pallet<T>>::block_number(),
+				actor.clone(),
+				action,
+				product,
+			);
+			<ProductHistory<T>>::mutate(id, |history| {
+				if history.len() as u32 >= T::MaxHistoryLength::get() && !history.is_empty() {
+					history.remove(0);
+				}
+				// only fails with a zero bound
+				let _ = history.try_push(entry);
+			});
+		}
+
+		pub fn is_suspended(who: &T::AccountId)-> bool{
+			match Self::get_suspension(who) {
+				Some(until)=> <frame_system::Pallet<T>>::block_number() < until,
+				None=> false
+			}
+		}
+
+		// ensure the signed account holds the given role
+		pub fn ensure_role(origin: OriginFor<T>, role: Role)-> DispatchResult{
+			Self::ensure_authorized(origin.clone())?;
+			let sender= ensure_signed(origin)?;
+			ensure!(Self::get_authorized_user(&sender).contains(role), Error::<T>::MissingRole);
+			Ok(())
+		}
+
+		// ensure `AdminOrigin` or an account with the admin role
+		pub fn ensure_admin(origin: OriginFor<T>)-> DispatchResult{
+			if T::AdminOrigin::ensure_origin(origin.clone()).is_ok() {
+				return Ok(());
+			}
+			Self::ensure_role(origin, Role::Admin)
+		}
+
 	}
 
 	//genesis_pallet_account
@@ -0,0 +1,367 @@
+//! Storage migrations of the product pallet.
+//!
+//! Every change to a stored layout bumps `STORAGE_VERSION` in `lib.rs` and adds a module `vN`
+//! here with a `MigrateToVN` upgrade which only runs while the on-chain version is `N - 1`. The
+//! runtime lists the upgrades in order in its `Migrations` tuple.
+//!
+//! Each `vN` module freezes the product layout of version `N` as `VNProduct`, so an upgrade
+//! writes the layout of its own version whatever the current `Product` looks like.
+//!
+//! An upgrade too large for one block keeps a cursor and is continued by `resume` from the
+//! pallet's `on_initialize`, which also runs the later upgrades once it is done.
+
+use crate::{
+    pallet::{Config, Pallet, Products, ProductCounter, ProductsByOwner, ProductsByPosition},
+    types::{BalanceOf, Product, ProductPositionEnum},
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    pallet_prelude::*,
+    storage::{unhashed, StoragePrefixedMap},
+    traits::OnRuntimeUpgrade,
+    Blake2_128, StorageHasher,
+};
+use sp_core::ConstU32;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+
+// v1 switches `Products` from `Blake2_128` to `Blake2_128Concat`, adds quantities, payment assets
+// and the manufacturer to stored products and fills the owner and position indexes
+pub mod v1 {
+    use super::*;
+    use crate::types::{AssetIdOf, PaymentAsset, ProductName};
+
+    // product layout before quantities, payment assets and the manufacturer were added
+    #[derive(Encode, Decode)]
+    pub struct OldProduct<T: Config> {
+        pub name: BoundedVec<u8, ConstU32<10>>,
+        pub price: BalanceOf<T>,
+        pub owner: T::AccountId,
+        pub is_sold: bool,
+        pub resale: bool,
+        pub position: ProductPositionEnum,
+    }
+
+    // product layout of version 1
+    #[derive(Encode, Decode)]
+    pub struct V1Product<T: Config> {
+        pub name: ProductName<T>,
+        pub price: BalanceOf<T>,
+        pub quantity: u32,
+        pub asset: PaymentAsset<AssetIdOf<T>>,
+        pub owner: T::AccountId,
+        pub manufacturer: T::AccountId,
+        pub is_sold: bool,
+        pub resale: bool,
+        pub position: ProductPositionEnum,
+    }
+
+    // next id to probe while the migration spans several blocks
+    #[frame_support::storage_alias]
+    pub type MigrationCursor<T: Config> = StorageValue<Pallet<T>, u128>;
+
+    // ids probed per block, each one costs a read and up to four writes
+    pub const IDS_PER_BLOCK: u128 = 500;
+
+    // storage key of a product under the old non reversible hasher
+    pub fn old_product_key<T: Config>(id: u128)-> Vec<u8>{
+        let mut key = Products::<T>::final_prefix().to_vec();
+        key.extend_from_slice(&Blake2_128::hash(&id.encode()));
+        key
+    }
+
+    // moves the products of at most `limit` ids from the cursor on to the new keys. The old keys
+    // do not reveal their id, so ids up to the counter are probed instead of draining the prefix.
+    // Version 1 is set once the counter is reached
+    pub fn migrate_batch<T: Config>(limit: u128)-> Weight{
+        let counter = ProductCounter::<T>::get().unwrap_or(0);
+        let start = MigrationCursor::<T>::get().unwrap_or(1);
+        let end = counter.min(start.saturating_add(limit).saturating_sub(1));
+        let mut migrated: u64 = 0;
+
+        for id in start..=end {
+            if let Some(old) = unhashed::take::<OldProduct<T>>(&old_product_key::<T>(id)) {
+                let mut name = old.name.into_inner();
+                name.truncate(T::MaxNameLength::get() as usize);
+
+                // the creator is unknown, the owner is the best guess for the manufacturer
+                let p = V1Product::<T>{
+                    name: BoundedVec::try_from(name).unwrap_or_default(),
+                    price: old.price,
+                    quantity: 1,
+                    asset: PaymentAsset::Native,
+                    owner: old.owner.clone(),
+                    manufacturer: old.owner,
+                    is_sold: old.is_sold,
+                    resale: old.resale,
+                    position: old.position,
+                };
+
+                ProductsByOwner::<T>::insert(&p.owner, id, ());
+                ProductsByPosition::<T>::insert(p.position.kind(), id, ());
+                unhashed::put(&Products::<T>::hashed_key_for(id), &p);
+                migrated += 1;
+            }
+        }
+
+        if end < counter {
+            MigrationCursor::<T>::put(end + 1);
+        } else {
+            MigrationCursor::<T>::kill();
+            StorageVersion::new(1).put::<Pallet<T>>();
+        }
+
+        let probed = end.saturating_add(1).saturating_sub(start) as u64;
+        T::DbWeight::get().reads_writes(2 + probed, 1 + 4 * migrated)
+    }
+
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade()-> Weight{
+            if Pallet::<T>::on_chain_storage_version() >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            // the ids beyond the first batch follow in `resume`
+            migrate_batch::<T>(IDS_PER_BLOCK).saturating_add(T::DbWeight::get().reads(1))
+        }
+
+        // owners of every product stored under the old keys
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade()-> Result<Vec<u8>, &'static str>{
+            if Pallet::<T>::on_chain_storage_version() >= 1 {
+                return Ok(Vec::<(u128, T::AccountId)>::new().encode());
+            }
+
+            let counter = ProductCounter::<T>::get().unwrap_or(0);
+            let owners: Vec<(u128, T::AccountId)> = (1..=counter)
+                .filter_map(|id| {
+                    unhashed::get::<OldProduct<T>>(&old_product_key::<T>(id)).map(|old| (id, old.owner))
+                })
+                .collect();
+
+            Ok(owners.encode())
+        }
+
+        // every product up to the cursor is readable under the new key with the same owner and is
+        // indexed, the rest is still under its old key
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>)-> Result<(), &'static str>{
+            let owners = Vec::<(u128, T::AccountId)>::decode(&mut &state[..])
+                .map_err(|_| "v1: cannot decode pre upgrade state")?;
+
+            let cursor = MigrationCursor::<T>::get();
+            ensure!(
+                cursor.is_some() || Pallet::<T>::on_chain_storage_version() >= 1,
+                "v1: storage version not set"
+            );
+
+            for (id, owner) in owners.iter() {
+                if cursor.map_or(false, |next| *id >= next) {
+                    ensure!(unhashed::exists(&old_product_key::<T>(*id)), "v1: pending product lost");
+                    continue;
+                }
+
+                ensure!(unhashed::get_raw(&old_product_key::<T>(*id)).is_none(), "v1: old key left behind");
+
+                let p = unhashed::get::<V1Product<T>>(&Products::<T>::hashed_key_for(id))
+                    .ok_or("v1: product lost")?;
+                ensure!(&p.owner == owner, "v1: owner changed");
+                ensure!(ProductsByOwner::<T>::contains_key(owner, id), "v1: owner index missing");
+                ensure!(ProductsByPosition::<T>::contains_key(p.position.kind(), id), "v1: position index missing");
+            }
+
+            ensure!(
+                cursor.is_some() || ProductsByOwner::<T>::iter().count() >= owners.len(),
+                "v1: product count changed"
+            );
+            Ok(())
+        }
+    }
+}
+
+
+// v2 adds the purchase block to stored products, they were bought before returns existed
+pub mod v2 {
+    use super::*;
+    use crate::types::{AssetIdOf, PaymentAsset, ProductName};
+    use v1::V1Product;
+
+    // product layout of version 2
+    #[derive(Encode, Decode)]
+    pub struct V2Product<T: Config> {
+        pub name: ProductName<T>,
+        pub price: BalanceOf<T>,
+        pub quantity: u32,
+        pub asset: PaymentAsset<AssetIdOf<T>>,
+        pub owner: T::AccountId,
+        pub manufacturer: T::AccountId,
+        pub is_sold: bool,
+        pub resale: bool,
+        pub position: ProductPositionEnum,
+        pub purchased_at: Option<T::BlockNumber>,
+    }
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade()-> Weight{
+            if Pallet::<T>::on_chain_storage_version() != 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            // `Products` only translates into the current layout, so version 2 is written by key
+            let ids: Vec<u128> = Products::<T>::iter_keys().collect();
+            let mut translated: u64 = 0;
+
+            for id in ids.iter() {
+                let key = Products::<T>::hashed_key_for(id);
+                if let Some(old) = unhashed::get::<V1Product<T>>(&key) {
+                    unhashed::put(&key, &V2Product::<T>{
+                        name: old.name,
+                        price: old.price,
+                        quantity: old.quantity,
+                        asset: old.asset,
+                        owner: old.owner,
+                        manufacturer: old.manufacturer,
+                        is_sold: old.is_sold,
+                        resale: old.resale,
+                        position: old.position,
+                        purchased_at: None,
+                    });
+                    translated += 1;
+                }
+            }
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(1 + 2 * ids.len() as u64, 1 + translated)
+        }
+
+        // owners of every stored product
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade()-> Result<Vec<u8>, &'static str>{
+            if Pallet::<T>::on_chain_storage_version() != 1 {
+                return Ok(Vec::<(u128, T::AccountId)>::new().encode());
+            }
+
+            let owners: Vec<(u128, T::AccountId)> = Products::<T>::iter_keys()
+                .filter_map(|id| {
+                    unhashed::get::<V1Product<T>>(&Products::<T>::hashed_key_for(id)).map(|old| (id, old.owner))
+                })
+                .collect();
+
+            Ok(owners.encode())
+        }
+
+        // every product decodes with the new layout, keeps its owner and none was bought yet
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>)-> Result<(), &'static str>{
+            let owners = Vec::<(u128, T::AccountId)>::decode(&mut &state[..])
+                .map_err(|_| "v2: cannot decode pre upgrade state")?;
+
+            // the upgrade waits for a v1 migration still in progress
+            if v1::MigrationCursor::<T>::exists() {
+                return Ok(());
+            }
+
+            ensure!(Pallet::<T>::on_chain_storage_version() == 2, "v2: storage version not set");
+
+            for (id, owner) in owners.iter() {
+                let p = unhashed::get::<V2Product<T>>(&Products::<T>::hashed_key_for(id))
+                    .ok_or("v2: product lost")?;
+                ensure!(&p.owner == owner, "v2: owner changed");
+                ensure!(p.purchased_at.is_none(), "v2: purchase block set");
+            }
+            Ok(())
+        }
+    }
+}
+
+
+// v3 adds the warranty period and expiry to stored products, none of them carries a warranty.
+// Version 3 is the current `Product` layout
+pub mod v3 {
+    use super::*;
+    use v2::V2Product;
+
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade()-> Weight{
+            if Pallet::<T>::on_chain_storage_version() != 2 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated: u64 = 0;
+            Products::<T>::translate::<V2Product<T>, _>(|_, old| {
+                translated += 1;
+
+                let mut p = Product::<T>::new(old.name, old.price, old.manufacturer, old.position);
+                p.set_owner(old.owner);
+                p.set_quantity(old.quantity);
+                p.set_asset(old.asset);
+                p.set_is_sold(old.is_sold);
+                p.set_resale(old.resale);
+                p.set_purchased_at(old.purchased_at);
+                Some(p)
+            });
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(1 + translated, 1 + translated)
+        }
+
+        // owners of every stored product
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade()-> Result<Vec<u8>, &'static str>{
+            if Pallet::<T>::on_chain_storage_version() != 2 {
+                return Ok(Vec::<(u128, T::AccountId)>::new().encode());
+            }
+
+            let owners: Vec<(u128, T::AccountId)> = Products::<T>::iter_keys()
+                .filter_map(|id| {
+                    unhashed::get::<V2Product<T>>(&Products::<T>::hashed_key_for(id)).map(|old| (id, old.owner))
+                })
+                .collect();
+
+            Ok(owners.encode())
+        }
+
+        // every product decodes with the new layout, keeps its owner and has no warranty
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>)-> Result<(), &'static str>{
+            let owners = Vec::<(u128, T::AccountId)>::decode(&mut &state[..])
+                .map_err(|_| "v3: cannot decode pre upgrade state")?;
+
+            // the upgrade waits for a v1 migration still in progress
+            if v1::MigrationCursor::<T>::exists() {
+                return Ok(());
+            }
+
+            ensure!(Pallet::<T>::on_chain_storage_version() == 3, "v3: storage version not set");
+
+            for (id, owner) in owners.iter() {
+                let mut p = Products::<T>::get(id).ok_or("v3: product lost")?;
+                ensure!(&p.get_owner() == owner, "v3: owner changed");
+                ensure!(p.get_warranty_period().is_none(), "v3: warranty set");
+            }
+            Ok(())
+        }
+    }
+}
+
+
+
+// continues a v1 migration which did not fit into the upgrade block by one batch per block. The
+// later upgrades were skipped while it was pending and run in the block it completes
+pub fn resume<T: Config>()-> Weight{
+    if !v1::MigrationCursor::<T>::exists() {
+        return T::DbWeight::get().reads(1);
+    }
+
+    v1::migrate_batch::<T>(v1::IDS_PER_BLOCK)
+        .saturating_add(v2::MigrateToV2::<T>::on_runtime_upgrade())
+        .saturating_add(v3::MigrateToV3::<T>::on_runtime_upgrade())
+}
@@ -1,6 +1,7 @@
-use crate::{self as pallet_product, types::AccountIdOf};
-use frame_support::{traits::{ConstU16, ConstU64}, parameter_types};
+use crate::{self as pallet_product, types::AccountIdOf, traits::DefaultTransitionRules};
+use frame_support::{traits::{ConstU16, ConstU64, EitherOfDiverse}, ord_parameter_types, parameter_types, PalletId};
 use frame_system as system;
+use frame_system::{EnsureRoot, EnsureSignedBy};
 
 pub const TEST_OWNER_ACCOUNT:u64=100;
 
@@ -8,6 +9,7 @@ use sp_core::{H256, ConstU32};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
 };
 
 
@@ -26,6 +28,7 @@ frame_support::construct_runtime!(
 		System: frame_system,
 		ProductModule: pallet_product,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 	}
 );
 
@@ -58,12 +61,47 @@ impl system::Config for Test {
 
 parameter_types! {
 	pub const ExistentialDeposit: u64 = 500;
+	pub static EscrowEnabled: bool = false;
+	pub static ProductDeposit: u64 = 0;
+	pub const ResaleRoyalty: Perbill = Perbill::from_percent(10);
+	pub static MarketplaceFee: Perbill = Perbill::from_percent(10);
+	pub const ProductPalletId: PalletId = PalletId(*b"py/prdct");
 }
 
 
+ord_parameter_types! {
+	pub const ProductAdmin: u64 = TEST_OWNER_ACCOUNT;
+}
+
 impl pallet_product::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type AssetId = u32;
+	type Assets = Assets;
+	type PalletId = ProductPalletId;
+	type ProductDeposit = ProductDeposit;
+	type MaxNameLength = ConstU32<32>;
+	type MaxDescriptionLength = ConstU32<256>;
+	type TransitionRules = DefaultTransitionRules;
+	type WeightInfo = ();
+	type MaxHistoryLength = ConstU32<100>;
+	type ResaleRoyalty = ResaleRoyalty;
+	type EscrowEnabled = EscrowEnabled;
+	type EscrowTimeout = ConstU64<10>;
+	type MaxEscrowsPerBlock = ConstU32<10>;
+	type MaxResaleExpiriesPerBlock = ConstU32<10>;
+	type MaxOffersPerBlock = ConstU32<10>;
+	type MaxOffersPerProduct = ConstU32<3>;
+	type MaxAuctionsPerBlock = ConstU32<10>;
+	type AdminOrigin = EitherOfDiverse<EnsureRoot<u64>, EnsureSignedBy<ProductAdmin, u64>>;
+	type RegulatorOrigin = EnsureRoot<u64>;
+	type MaxRecallBatch = ConstU32<10>;
+	type MarketplaceFee = MarketplaceFee;
+	type MaxSplits = ConstU32<3>;
+	type ReturnPeriod = ConstU64<10>;
+	type MaxClaimsPerProduct = ConstU32<3>;
+	type ReturnResponsePeriod = ConstU64<5>;
+	type MaxReturnsPerBlock = ConstU32<10>;
 }
 
 impl pallet_balances::Config for Test {
@@ -78,6 +116,23 @@ impl pallet_balances::Config for Test {
 	type ReserveIdentifier = [u8; 8];
 }
 
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU64<1>;
+	type AssetAccountDeposit = ConstU64<1>;
+	type MetadataDepositBase = ConstU64<1>;
+	type MetadataDepositPerByte = ConstU64<1>;
+	type ApprovalDeposit = ConstU64<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	system::GenesisConfig::default()
@@ -1,8 +1,10 @@
 use core::{ str::Bytes};
 
-use crate::{mock::{*, self}, Error, types::{ self, Product, ProductPositionEnum}};
-use frame_support::{ assert_ok, BoundedVec, assert_err};
+use crate::{mock::{*, self}, Error, types::{ self, Product, ProductPositionEnum, PositionKind, Role, ProductAction, PaymentAsset, ProductMetadata, AuctionKind, RecallTarget, ClaimOutcome}};
+use frame_support::{ assert_ok, BoundedVec, assert_err, traits::Hooks};
 use crate as pallet_product;
+use sp_core::H256;
+use sp_runtime::Perbill;
 
 
 #[test]
@@ -12,14 +14,21 @@ fn it_works_for_add_authorized_user() {
 
         //Add one account_id using root_user
 		assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
-        assert_eq!(ProductModule::get_authorized_user(1),true);
+        assert!(ProductModule::get_authorized_user(1).contains(Role::Manufacturer));
 
-        // add onother account_id with existing user
+        // a user without the admin role cannot add others
+        assert_err!(
+            ProductModule::add_authorized_user(RuntimeOrigin::signed(1), 2),
+            Error::<Test>::MissingRole
+        );
+
+        // add onother account_id with existing admin user
+		assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Admin));
 		assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::signed(1), 2));
-        assert_eq!(ProductModule::get_authorized_user(2),true);
+        assert!(ProductModule::get_authorized_user(2).contains(Role::Manufacturer));
         
         //fail case
-        assert_eq!(ProductModule::get_authorized_user(3),false);
+        assert!(ProductModule::get_authorized_user(3).is_empty());
 	});
 }
 
@@ -27,22 +36,46 @@ fn it_works_for_add_authorized_user() {
 fn add_authorized_user_withOwner_account(){
     minimal_test_ext().execute_with(||{
 		assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::signed(TEST_OWNER_ACCOUNT), 2));
-        assert_eq!(ProductModule::get_authorized_user(2),true);
+        assert!(ProductModule::get_authorized_user(2).contains(Role::Manufacturer));
+    })
+}
+
+#[test]
+fn admin_origin_rotates_product_owner_account(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+
+        assert_err!(
+            ProductModule::set_product_owner_account(RuntimeOrigin::signed(1), 7),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(ProductModule::set_product_owner_account(RuntimeOrigin::signed(TEST_OWNER_ACCOUNT), 7));
+        assert_ok!(ProductModule::set_product_owner_account(RuntimeOrigin::root(), 8));
+        assert_eq!(ProductModule::get_product_owner_account(), Some(8));
+        System::assert_last_event(crate::Event::ProductOwnerAccountSet{ account: 8 }.into());
+
+        //the marketplace fee goes to the new account
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 8, 1000, 0));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_eq!(Balances::free_balance(8), 1100);
     })
 }
 
 #[test]
 fn it_works_product_add() {
 	new_test_ext().execute_with(  || {
+        System::set_block_number(1);
 		
         // Ensure the expected error is thrown when no value is present.
 		assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
 
         let mut vec = BoundedVec::try_from(Vec::from("other")).unwrap();
         let p =  types::Product::<Test>::new(vec.clone(),4,1, types::ProductPositionEnum::Manufacture,);
-        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), vec, 4 ));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), vec, 4, None, None));
         let savedP:Product<Test>=ProductModule::get_product_info(1).unwrap(); 
         assert_eq!(savedP, p);
+        System::assert_last_event(crate::Event::ProductCreated{ id: 1, manufacturer: 1, price: 4, quantity: 1 }.into());
         
 	});
 }
@@ -51,20 +84,35 @@ fn it_works_product_add() {
 fn it_works_product_position_update(){
 
     new_test_ext().execute_with(||{
+        System::set_block_number(1);
         assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
         let mut vec = BoundedVec::try_from(Vec::from("other")).unwrap();
         
-        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), vec, 4));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), vec, 4, None, None));
+
+        //only a distributor can move goods into distribution
+        assert_err!(
+            ProductModule::update_position(RuntimeOrigin::signed(1),1, ProductPositionEnum::Distribution),
+            Error::<Test>::MissingRole
+        );
 
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
         assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1),1, ProductPositionEnum::Distribution));
         let mut p: Product<Test> = ProductModule::get_product_info(1).unwrap();
-        assert_eq!(p.get_position(), ProductPositionEnum::Retailer);
+        assert_eq!(p.get_position(), ProductPositionEnum::Distribution);
+        System::assert_last_event(crate::Event::PositionUpdated{
+            id: 1,
+            actor: 1,
+            from: ProductPositionEnum::Manufacture,
+            to: ProductPositionEnum::Distribution,
+        }.into());
     })
 }
 
 #[test]
 fn it_works_buy_product(){
     minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
         //balance transfer to owner account just for testing 
         //bacause of existential deposit value 
         assert_ok!(
@@ -76,8 +124,12 @@ fn it_works_buy_product(){
             )
         );
 
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+
         //adding authorized user
         assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer));
 
         let buyer=2;
      
@@ -85,16 +137,21 @@ fn it_works_buy_product(){
         assert_ok!(ProductModule::add_product(
             RuntimeOrigin::signed(1), 
             BoundedVec::try_from(Vec::from("other")).unwrap(), 
-            20u32.into())
+            20u32.into(), None, None)
         );
 
         //updating position
-        //sell_product is not allowed without position distribution
+        //sell_product is not allowed without position retailer
         assert_ok!(
             ProductModule::update_position(RuntimeOrigin::signed(1),
             1, 
             ProductPositionEnum::Distribution)
         );
+        assert_ok!(
+            ProductModule::update_position(RuntimeOrigin::signed(1),
+            1, 
+            ProductPositionEnum::Retailer)
+        );
         
         //set balance of buyer for payment 
         assert_ok!(
@@ -106,12 +163,13 @@ fn it_works_buy_product(){
             )
         );
             
-        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
 
         assert_eq!(
             ProductModule::get_product_info(1).unwrap().get_owner(), 
             buyer
         );
+        System::assert_last_event(crate::Event::ProductSold{ id: 1, seller: 1, buyer, price: 20, quantity: 1 }.into());
     })
 }
 
@@ -119,6 +177,7 @@ fn it_works_buy_product(){
 #[test]
 fn it_works_update_resale(){
     minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
 
         //balance transfer to owner account just for testing 
         //bacause of existential deposit value 
@@ -131,18 +190,27 @@ fn it_works_update_resale(){
             )
         );
 
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+
         //adding authorized user
         assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer));
 
         //add a test product
         assert_ok!(ProductModule::add_product(
             RuntimeOrigin::signed(1), 
             BoundedVec::try_from(Vec::from("other")).unwrap(), 
-            20u32.into())
+            20u32.into(), None, None)
         );
        
         //updating position
-        //sell_product is not allowed without position distribution
+        //sell_product is not allowed without position retailer
+        assert_ok!(
+            ProductModule::update_position(RuntimeOrigin::signed(1),
+            1, 
+            ProductPositionEnum::Distribution)
+        );
         assert_ok!(
             ProductModule::update_position(RuntimeOrigin::signed(1),
             1, 
@@ -161,13 +229,1420 @@ fn it_works_update_resale(){
             )
         );
             
-        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
 
-        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 100));
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 100, None));
 
         let mut p:Product<Test>= ProductModule::get_product_info(1).unwrap();
         assert_eq!(p.get_price(), 100);
         assert_eq!(p.get_resale(), true);    
+        System::assert_last_event(crate::Event::ResaleEnabled{ id: 1, owner: 2, price: 100, expiry: None }.into());
+
+    })
+}
+
+
+#[test]
+fn grant_and_revoke_role(){
+    new_test_ext().execute_with(||{
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer));
+        assert!(ProductModule::get_authorized_user(1).contains(Role::Retailer));
+        assert_err!(
+            ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer),
+            Error::<Test>::RoleAlreadyGranted
+        );
+
+        // non admin cannot change roles
+        assert_err!(
+            ProductModule::grant_role(RuntimeOrigin::signed(1), 1, Role::Admin),
+            Error::<Test>::MissingRole
+        );
+
+        assert_ok!(ProductModule::revoke_role(RuntimeOrigin::root(), 1, Role::Retailer));
+        assert!(!ProductModule::get_authorized_user(1).contains(Role::Retailer));
+
+        //removing the last role removes the account
+        assert_ok!(ProductModule::revoke_role(RuntimeOrigin::root(), 1, Role::Manufacturer));
+        assert!(ProductModule::get_authorized_user(1).is_empty());
+        assert_err!(
+            ProductModule::revoke_role(RuntimeOrigin::root(), 1, Role::Manufacturer),
+            Error::<Test>::RoleNotGranted
+        );
+    })
+}
+
+#[test]
+fn remove_authorized_user_works(){
+    new_test_ext().execute_with(||{
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+
+        //only the admin origin or an admin can remove
+        assert_err!(
+            ProductModule::remove_authorized_user(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::MissingRole
+        );
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 2));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 2, Role::Admin));
+        assert_ok!(ProductModule::remove_authorized_user(RuntimeOrigin::signed(2), 2));
+        assert!(ProductModule::get_authorized_user(2).is_empty());
+
+        assert_ok!(ProductModule::remove_authorized_user(RuntimeOrigin::root(), 1));
+        assert!(ProductModule::get_authorized_user(1).is_empty());
+        assert_err!(
+            ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_err!(
+            ProductModule::remove_authorized_user(RuntimeOrigin::root(), 1),
+            Error::<Test>::AuthorizedUserDonotExist
+        );
+    })
+}
+
+#[test]
+fn suspended_user_is_rejected_until_block(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+
+        assert_ok!(ProductModule::suspend_authorized_user(RuntimeOrigin::signed(TEST_OWNER_ACCOUNT), 1, 5));
+        assert_err!(
+            ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None),
+            Error::<Test>::UserSuspended
+        );
+
+        //suspension lapses at the given block
+        System::set_block_number(5);
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None));
+
+        //an account with the admin role can suspend too
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 2));
+        assert_err!(
+            ProductModule::suspend_authorized_user(RuntimeOrigin::signed(2), 1, 10),
+            Error::<Test>::MissingRole
+        );
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 2, Role::Admin));
+        assert_ok!(ProductModule::suspend_authorized_user(RuntimeOrigin::signed(2), 1, 10));
+        assert_eq!(ProductModule::get_suspension(1), Some(10));
+    })
+}
+
+#[test]
+fn update_position_follows_transition_rules(){
+    new_test_ext().execute_with(||{
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None));
+
+        //distribution cannot be skipped
+        assert_err!(
+            ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Retailer),
+            Error::<Test>::InvalidTransition
+        );
+
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Distribution));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Retailer));
+
+        //no way back to manufacture
+        assert_err!(
+            ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Manufacture),
+            Error::<Test>::InvalidTransition
+        );
+    })
+}
+
+#[test]
+fn logistics_positions_and_terminal_states(){
+    new_test_ext().execute_with(||{
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None));
+
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::InTransit{ carrier: 7 }));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Warehouse{ facility_id: 3 }));
+        assert_eq!(
+            ProductModule::get_product_info(1).unwrap().get_position(),
+            ProductPositionEnum::Warehouse{ facility_id: 3 }
+        );
+        //every warehouse is listed under one key
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 2, ProductPositionEnum::Distribution));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 2, ProductPositionEnum::Warehouse{ facility_id: 8 }));
+        assert_eq!(ProductModule::products_by_position(PositionKind::Warehouse), vec![1, 2]);
+
+        //a recall goes through the regulator
+        assert_err!(
+            ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Recalled),
+            Error::<Test>::RecallNeedsRecallProduct
+        );
+
+        //another manufacturer can not destroy the product
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 3));
+        assert_err!(
+            ProductModule::update_position(RuntimeOrigin::signed(3), 1, ProductPositionEnum::Destroyed),
+            Error::<Test>::NotManufacturer
+        );
+
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Destroyed));
+
+        //destroyed is terminal
+        assert_err!(
+            ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Retailer),
+            Error::<Test>::ProductIsTerminal
+        );
+
+        //only products at the retailer can be bought
+        assert_err!(
+            ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1),
+            Error::<Test>::NotReadyForRetailer
+        );
+    })
+}
+
+#[test]
+fn product_history_is_recorded(){
+    new_test_ext().execute_with(||{
+        System::set_block_number(1);
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None));
+
+        System::set_block_number(2);
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Distribution));
+
+        let history = ProductModule::product_history(1);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].action, ProductAction::Created);
+        assert_eq!(history[0].block_number, 1);
+        assert_eq!(history[0].position, ProductPositionEnum::Manufacture);
+        assert_eq!(history[1].action, ProductAction::PositionUpdated);
+        assert_eq!(history[1].block_number, 2);
+        assert_eq!(history[1].actor, 1);
+        assert_eq!(history[1].position, ProductPositionEnum::Distribution);
+
+        assert!(ProductModule::product_history(2).is_empty());
+    })
+}
+
+// adds product 1 at the retailer and funds the buyer 2
+fn setup_product_at_retailer(){
+    assert_ok!(
+        <Test as pallet_product::Config>::Currency::set_balance(
+            mock::RuntimeOrigin::root(),
+            TEST_OWNER_ACCOUNT,
+            20000,
+            0u32.into(),
+        )
+    );
+    assert_ok!(
+        <Test as pallet_product::Config>::Currency::set_balance(
+            mock::RuntimeOrigin::root(),
+            2,
+            2000000,
+            0u32.into(),
+        )
+    );
+
+    assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+    assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+    assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer));
+    assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 1000, None, None));
+    assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Distribution));
+    assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Retailer));
+}
+
+#[test]
+fn escrow_released_on_delivery_confirmation(){
+    minimal_test_ext().execute_with(||{
+        EscrowEnabled::set(true);
+        System::set_block_number(1);
+        setup_product_at_retailer();
+
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_eq!(Balances::reserved_balance(2), 1000);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 1);
+
+        assert_err!(
+            ProductModule::confirm_delivery(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NotShipped
+        );
+        assert_err!(
+            ProductModule::mark_shipped(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NotSeller
+        );
+        assert_ok!(ProductModule::mark_shipped(RuntimeOrigin::signed(1), 1));
+        assert_ok!(ProductModule::confirm_delivery(RuntimeOrigin::signed(2), 1));
+
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20100);
+        assert_eq!(Balances::free_balance(1), 900);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 2);
+        assert!(ProductModule::get_escrow(1).is_none());
+    })
+}
+
+#[test]
+fn escrow_settled_at_timeout(){
+    minimal_test_ext().execute_with(||{
+        EscrowEnabled::set(true);
+        System::set_block_number(1);
+        setup_product_at_retailer();
+
+        //never shipped, refunded at the deadline
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        ProductModule::on_initialize(11);
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 2000000);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 1);
+
+        //shipped but not confirmed, released at the deadline
+        System::set_block_number(20);
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_ok!(ProductModule::mark_shipped(RuntimeOrigin::signed(1), 1));
+        ProductModule::on_initialize(30);
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20100);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 2);
+    })
+}
+
+#[test]
+fn resale_pays_owner_and_manufacturer_royalty(){
+    minimal_test_ext().execute_with(||{
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+
+        //10% fee to the product owner account, the rest to the seller
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20100);
+        assert_eq!(Balances::free_balance(1), 10900);
+
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, None));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(3), 1, 1));
+
+        //10% fee, 10% royalty to the manufacturer, the rest to the reselling owner
+        assert_eq!(Balances::free_balance(1), 10950);
+        assert_eq!(Balances::free_balance(2), 2000000 - 1000 + 400);
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20150);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 3);
+    })
+}
+
+#[test]
+fn first_sale_is_split_between_fee_shares_and_seller(){
+    minimal_test_ext().execute_with(||{
+        setup_product_at_retailer();
+        for who in [1, 3, 4] {
+            assert_ok!(Balances::set_balance(RuntimeOrigin::root(), who, 10000, 0));
+        }
+
+        //shares can not add up to more than the whole price
+        let too_much = BoundedVec::try_from(vec![(3, Perbill::from_percent(60)), (4, Perbill::from_percent(50))]).unwrap();
+        assert_err!(
+            ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 1000, Some(too_much), None),
+            Error::<Test>::InvalidSplits
+        );
+
+        //distributor 3 and retailer 4 share the first sale with the manufacturer
+        let splits = BoundedVec::try_from(vec![(3, Perbill::from_percent(20)), (4, Perbill::from_percent(30))]).unwrap();
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 1000, Some(splits.clone()), None));
+        assert_eq!(ProductModule::get_product_splits(2), Some(splits));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 2, ProductPositionEnum::Distribution));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 2, ProductPositionEnum::Retailer));
+
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 2, 1));
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20100);
+        assert_eq!(Balances::free_balance(3), 10180);
+        assert_eq!(Balances::free_balance(4), 10270);
+        assert_eq!(Balances::free_balance(1), 10450);
+
+        //a resale only pays the fee and the royalty
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 2, 1000, None));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(4), 2, 1));
+        assert_eq!(Balances::free_balance(3), 10180);
+        assert_eq!(Balances::free_balance(4), 10270 - 1000);
+        assert_eq!(Balances::free_balance(1), 10550);
+
+        //without a fee the seller gets the whole price
+        MarketplaceFee::set(Perbill::zero());
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20200);
+        assert_eq!(Balances::free_balance(1), 11550);
+    })
+}
+
+#[test]
+fn disable_and_reprice_resale_listing(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        assert_err!(
+            ProductModule::disable_resale(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NotInResaleList
+        );
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, None));
+
+        //only the owner can change the listing
+        assert_err!(
+            ProductModule::update_resale_price(RuntimeOrigin::signed(3), 1, 400),
+            Error::<Test>::BadOrigin
+        );
+        assert_err!(
+            ProductModule::disable_resale(RuntimeOrigin::signed(3), 1),
+            Error::<Test>::BadOrigin
+        );
+
+        assert_ok!(ProductModule::update_resale_price(RuntimeOrigin::signed(2), 1, 400));
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_price(), 400);
+        System::assert_last_event(crate::Event::ResalePriceUpdated{ id: 1, owner: 2, price: 400 }.into());
+
+        assert_ok!(ProductModule::disable_resale(RuntimeOrigin::signed(2), 1));
+        assert!(!ProductModule::get_product_info(1).unwrap().get_resale());
+        System::assert_last_event(crate::Event::ResaleDisabled{ id: 1, owner: 2 }.into());
+        assert_err!(
+            ProductModule::buy_product(RuntimeOrigin::signed(3), 1, 1),
+            Error::<Test>::NotInResaleList
+        );
+        assert_err!(
+            ProductModule::update_resale_price(RuntimeOrigin::signed(2), 1, 300),
+            Error::<Test>::NotInResaleList
+        );
+    })
+}
+
+#[test]
+fn resale_listing_lapses_at_expiry(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        assert_err!(
+            ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, Some(1)),
+            Error::<Test>::InvalidExpiry
+        );
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, Some(10)));
+        //relisting replaces the previous expiry
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, Some(5)));
+        assert_eq!(ProductModule::get_resale_expiry(1), Some(5));
+
+        //a price update keeps the expiry
+        assert_ok!(ProductModule::update_resale_price(RuntimeOrigin::signed(2), 1, 450));
+        assert_eq!(ProductModule::get_resale_expiry(1), Some(5));
+
+        ProductModule::on_initialize(4);
+        assert!(ProductModule::get_product_info(1).unwrap().get_resale());
+
+        System::set_block_number(5);
+        ProductModule::on_initialize(5);
+        assert!(!ProductModule::get_product_info(1).unwrap().get_resale());
+        assert!(ProductModule::get_resale_expiry(1).is_none());
+        System::assert_last_event(crate::Event::ResaleExpired{ id: 1 }.into());
+        assert_err!(
+            ProductModule::buy_product(RuntimeOrigin::signed(3), 1, 1),
+            Error::<Test>::NotInResaleList
+        );
+
+        //the old expiry no longer touches a new listing
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, None));
+        ProductModule::on_initialize(10);
+        assert!(ProductModule::get_product_info(1).unwrap().get_resale());
+
+        //a sale clears the expiry of the listing
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, Some(20)));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(3), 1, 1));
+        assert!(ProductModule::get_resale_expiry(1).is_none());
+    })
+}
+
+#[test]
+fn transfer_product_without_payment(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+
+        //goods still in the supply chain only move through a sale
+        assert_err!(
+            ProductModule::transfer_product(RuntimeOrigin::signed(1), 1, 3),
+            Error::<Test>::ProductIsNotSold
+        );
+
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, Some(10)));
+
+        assert_err!(
+            ProductModule::transfer_product(RuntimeOrigin::signed(3), 1, 4),
+            Error::<Test>::BadOrigin
+        );
+        assert_err!(
+            ProductModule::transfer_product(RuntimeOrigin::signed(2), 1, 2),
+            Error::<Test>::TransferToSelf
+        );
+
+        let balance = Balances::free_balance(2);
+        assert_ok!(ProductModule::transfer_product(RuntimeOrigin::signed(2), 1, 3));
+        let mut p = ProductModule::get_product_info(1).unwrap();
+        assert_eq!(p.get_owner(), 3);
+        assert!(!p.get_resale());
+        assert!(ProductModule::get_resale_expiry(1).is_none());
+        assert_eq!(Balances::free_balance(2), balance);
+        assert_eq!(ProductModule::products_by_owner(&3), vec![1]);
+        System::assert_last_event(crate::Event::ProductTransferred{ id: 1, from: 2, to: 3 }.into());
+
+        let history = ProductModule::product_history(1);
+        let last = history.last().unwrap();
+        assert_eq!(last.action, ProductAction::Transferred);
+        assert_eq!(last.actor, 2);
+        assert_eq!(last.owner, 3);
+    })
+}
+
+#[test]
+fn two_step_transfer_needs_acceptance(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        assert_ok!(ProductModule::propose_transfer(RuntimeOrigin::signed(2), 1, 3));
+        System::assert_last_event(crate::Event::TransferProposed{ id: 1, from: 2, to: 3 }.into());
+        //nothing moves before the recipient accepts
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 2);
+
+        assert_err!(
+            ProductModule::accept_transfer(RuntimeOrigin::signed(4), 1),
+            Error::<Test>::NotRecipient
+        );
+
+        //a new proposal replaces the mistyped one
+        assert_ok!(ProductModule::propose_transfer(RuntimeOrigin::signed(2), 1, 4));
+        assert_err!(
+            ProductModule::accept_transfer(RuntimeOrigin::signed(3), 1),
+            Error::<Test>::NotRecipient
+        );
+
+        assert_err!(
+            ProductModule::cancel_transfer(RuntimeOrigin::signed(4), 1),
+            Error::<Test>::BadOrigin
+        );
+        assert_ok!(ProductModule::cancel_transfer(RuntimeOrigin::signed(2), 1));
+        assert_err!(
+            ProductModule::accept_transfer(RuntimeOrigin::signed(4), 1),
+            Error::<Test>::NoPendingTransfer
+        );
+
+        assert_ok!(ProductModule::propose_transfer(RuntimeOrigin::signed(2), 1, 4));
+        assert_ok!(ProductModule::accept_transfer(RuntimeOrigin::signed(4), 1));
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 4);
+        assert!(ProductModule::get_pending_transfer(1).is_none());
+        System::assert_last_event(crate::Event::ProductTransferred{ id: 1, from: 2, to: 4 }.into());
+    })
+}
+
+#[test]
+fn offers_are_held_until_accepted_or_rejected(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 4, 2000000, 0));
+
+        assert_err!(
+            ProductModule::make_offer(RuntimeOrigin::signed(3), 1, 800, 10),
+            Error::<Test>::ProductIsNotSold
+        );
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        assert_err!(
+            ProductModule::make_offer(RuntimeOrigin::signed(2), 1, 800, 10),
+            Error::<Test>::OwnProduct
+        );
+        assert_err!(
+            ProductModule::make_offer(RuntimeOrigin::signed(3), 1, 0, 10),
+            Error::<Test>::InvalidAmount
+        );
+        assert_err!(
+            ProductModule::make_offer(RuntimeOrigin::signed(3), 1, 800, 1),
+            Error::<Test>::InvalidExpiry
+        );
+
+        //a product does not need a resale listing to receive offers
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(3), 1, 800, 10));
+        System::assert_last_event(crate::Event::OfferMade{ id: 1, bidder: 3, amount: 800, expiry: 10 }.into());
+        //a new offer replaces the previous one
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(3), 1, 900, 10));
+        assert_eq!(Balances::reserved_balance(3), 900);
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(4), 1, 700, 10));
+        assert_eq!(Balances::reserved_balance(4), 700);
+
+        assert_err!(
+            ProductModule::reject_offer(RuntimeOrigin::signed(3), 1, 4),
+            Error::<Test>::BadOrigin
+        );
+        assert_ok!(ProductModule::reject_offer(RuntimeOrigin::signed(2), 1, 4));
+        assert_eq!(Balances::reserved_balance(4), 0);
+        assert!(ProductModule::get_offer(1, 4).is_none());
+        assert_err!(
+            ProductModule::accept_offer(RuntimeOrigin::signed(2), 1, 4),
+            Error::<Test>::OfferDonotExist
+        );
+
+        let seller_balance = Balances::free_balance(2);
+        assert_ok!(ProductModule::accept_offer(RuntimeOrigin::signed(2), 1, 3));
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 3);
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert_eq!(Balances::free_balance(3), 2000000 - 900);
+        //10% fee, 10% royalty to the manufacturer, the rest to the owner
+        assert_eq!(Balances::free_balance(1), 10990);
+        assert_eq!(Balances::free_balance(2), seller_balance + 720);
+        System::assert_has_event(crate::Event::ProductSold{ id: 1, seller: 2, buyer: 3, price: 900, quantity: 1 }.into());
+        System::assert_last_event(crate::Event::OfferAccepted{ id: 1, bidder: 3, amount: 900 }.into());
+    })
+}
+
+#[test]
+fn expired_offers_are_returned(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(3), 1, 800, 5));
+        ProductModule::on_initialize(4);
+        assert_eq!(Balances::reserved_balance(3), 800);
+
+        System::set_block_number(5);
+        ProductModule::on_initialize(5);
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert!(ProductModule::get_offer(1, 3).is_none());
+        System::assert_last_event(crate::Event::OfferExpired{ id: 1, bidder: 3 }.into());
+
+        //removing a product returns its open offers
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(3), 1, 800, 10));
+        assert_ok!(ProductModule::remove_product(RuntimeOrigin::signed(2), 1));
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert!(ProductModule::get_offer(1, 3).is_none());
+    })
+}
+
+#[test]
+fn offers_per_product_are_capped(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        for bidder in 3..7 {
+            assert_ok!(Balances::set_balance(RuntimeOrigin::root(), bidder, 2000000, 0));
+        }
+
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(3), 1, 800, 5));
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(4), 1, 800, 10));
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(5), 1, 800, 10));
+        assert_eq!(ProductModule::get_offer_count(1), 3);
+
+        assert_err!(
+            ProductModule::make_offer(RuntimeOrigin::signed(6), 1, 800, 10),
+            Error::<Test>::TooManyOffers
+        );
+        //replacing an offer does not count twice
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(5), 1, 900, 10));
+        assert_eq!(ProductModule::get_offer_count(1), 3);
+
+        //a rejected or expired offer frees its place
+        assert_ok!(ProductModule::reject_offer(RuntimeOrigin::signed(2), 1, 4));
+        System::set_block_number(5);
+        ProductModule::on_initialize(5);
+        assert_eq!(ProductModule::get_offer_count(1), 1);
+        assert_ok!(ProductModule::make_offer(RuntimeOrigin::signed(6), 1, 800, 10));
 
+        assert_ok!(ProductModule::remove_product(RuntimeOrigin::signed(2), 1));
+        assert_eq!(ProductModule::get_offer_count(1), 0);
     })
-}
\ No newline at end of file
+}
+
+#[test]
+fn approved_return_pays_back_every_payee(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_purchased_at(), Some(1));
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 1500, None));
+
+        assert_err!(
+            ProductModule::request_return(RuntimeOrigin::signed(3), 1),
+            Error::<Test>::BadOrigin
+        );
+        assert_ok!(ProductModule::request_return(RuntimeOrigin::signed(2), 1));
+        System::assert_last_event(crate::Event::ReturnRequested{ id: 1, buyer: 2, deadline: 6 }.into());
+        //the listing is withdrawn while the return is pending
+        assert!(!ProductModule::get_product_info(1).unwrap().get_resale());
+        assert_eq!(ProductModule::product_history(1).last().unwrap().action, ProductAction::ResaleDisabled);
+        assert_err!(
+            ProductModule::request_return(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::ProductInReturn
+        );
+        assert_err!(
+            ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 1500, None),
+            Error::<Test>::ProductInReturn
+        );
+        assert_err!(
+            ProductModule::transfer_product(RuntimeOrigin::signed(2), 1, 3),
+            Error::<Test>::ProductInReturn
+        );
+
+        assert_err!(
+            ProductModule::approve_return(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NotSeller
+        );
+        assert_ok!(ProductModule::approve_return(RuntimeOrigin::signed(1), 1));
+        System::assert_last_event(crate::Event::ReturnApproved{ id: 1, buyer: 2, amount: 1000 }.into());
+
+        //the fee and the seller share go back to the buyer
+        assert_eq!(Balances::free_balance(2), 2000000);
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20000);
+        assert_eq!(Balances::free_balance(1), 10000);
+
+        //a returned first sale can be sold again
+        let mut p = ProductModule::get_product_info(1).unwrap();
+        assert_eq!(p.get_owner(), 1);
+        assert!(!p.get_is_sold());
+        assert_eq!(p.get_purchased_at(), None);
+        assert!(ProductModule::get_last_sale(1).is_none());
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(3), 1, 1));
+    })
+}
+
+#[test]
+fn returns_end_with_the_period_or_a_rejection(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        System::set_block_number(12);
+        assert_err!(
+            ProductModule::request_return(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::ReturnPeriodOver
+        );
+
+        //the resale starts a new return period
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 500, None));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(3), 1, 1));
+        assert_err!(
+            ProductModule::approve_return(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NoReturnRequest
+        );
+        assert_ok!(ProductModule::request_return(RuntimeOrigin::signed(3), 1));
+
+        assert_ok!(ProductModule::reject_return(RuntimeOrigin::signed(2), 1));
+        System::assert_last_event(crate::Event::ReturnRejected{ id: 1, buyer: 3 }.into());
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 3);
+        assert_err!(
+            ProductModule::request_return(RuntimeOrigin::signed(3), 1),
+            Error::<Test>::NotReturnable
+        );
+
+        //an answered request is not approved at its deadline
+        ProductModule::on_initialize(17);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 3);
+    })
+}
+
+#[test]
+fn unanswered_returns_are_approved(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        System::set_block_number(3);
+        assert_ok!(ProductModule::request_return(RuntimeOrigin::signed(2), 1));
+        assert_eq!(ProductModule::get_return_request(1), Some(8));
+
+        ProductModule::on_initialize(7);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 2);
+
+        System::set_block_number(8);
+        ProductModule::on_initialize(8);
+        System::assert_last_event(crate::Event::ReturnApproved{ id: 1, buyer: 2, amount: 1000 }.into());
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 1);
+        assert_eq!(Balances::free_balance(2), 2000000);
+        assert!(ProductModule::get_return_request(1).is_none());
+    })
+}
+
+#[test]
+fn warranty_claims_while_the_warranty_lasts(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 1000, None, Some(20)));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 2, ProductPositionEnum::Distribution));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 2, ProductPositionEnum::Retailer));
+
+        //the warranty starts with the first sale
+        let evidence = H256::repeat_byte(3);
+        assert_err!(
+            ProductModule::file_warranty_claim(RuntimeOrigin::signed(1), 2, evidence),
+            Error::<Test>::NoWarranty
+        );
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 2, 1));
+        assert_eq!(ProductModule::get_product_info(2).unwrap().get_warranty_expiry(), Some(21));
+        assert_err!(
+            ProductModule::file_warranty_claim(RuntimeOrigin::signed(2), 1, evidence),
+            Error::<Test>::NoWarranty
+        );
+
+        assert_err!(
+            ProductModule::file_warranty_claim(RuntimeOrigin::signed(3), 2, evidence),
+            Error::<Test>::BadOrigin
+        );
+        assert_ok!(ProductModule::file_warranty_claim(RuntimeOrigin::signed(2), 2, evidence));
+        System::assert_last_event(crate::Event::WarrantyClaimFiled{ id: 2, claim: 0, owner: 2, evidence_hash: evidence }.into());
+        assert_err!(
+            ProductModule::file_warranty_claim(RuntimeOrigin::signed(2), 2, evidence),
+            Error::<Test>::ClaimPending
+        );
+
+        assert_err!(
+            ProductModule::resolve_claim(RuntimeOrigin::signed(2), 2, 0, ClaimOutcome::Repair),
+            Error::<Test>::NotManufacturer
+        );
+        assert_err!(
+            ProductModule::resolve_claim(RuntimeOrigin::signed(1), 2, 1, ClaimOutcome::Repair),
+            Error::<Test>::ClaimDonotExist
+        );
+        assert_ok!(ProductModule::resolve_claim(RuntimeOrigin::signed(1), 2, 0, ClaimOutcome::Replace));
+        System::assert_last_event(crate::Event::WarrantyClaimResolved{ id: 2, claim: 0, outcome: ClaimOutcome::Replace }.into());
+        assert_eq!(ProductModule::get_warranty_claim(2, 0).unwrap().outcome, Some(ClaimOutcome::Replace));
+        assert_err!(
+            ProductModule::resolve_claim(RuntimeOrigin::signed(1), 2, 0, ClaimOutcome::Refund),
+            Error::<Test>::ClaimResolved
+        );
+
+        //a resale keeps the running warranty
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 2, 500, None));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(3), 2, 1));
+        System::set_block_number(21);
+        assert_ok!(ProductModule::file_warranty_claim(RuntimeOrigin::signed(3), 2, evidence));
+        assert_eq!(ProductModule::get_claim_count(2), 2);
+        assert_ok!(ProductModule::resolve_claim(RuntimeOrigin::signed(1), 2, 1, ClaimOutcome::Refund));
+        assert_ok!(ProductModule::file_warranty_claim(RuntimeOrigin::signed(3), 2, evidence));
+        assert_ok!(ProductModule::resolve_claim(RuntimeOrigin::signed(1), 2, 2, ClaimOutcome::Repair));
+
+        //a product takes a bounded number of claims
+        assert_err!(
+            ProductModule::file_warranty_claim(RuntimeOrigin::signed(3), 2, evidence),
+            Error::<Test>::TooManyClaims
+        );
+
+        System::set_block_number(22);
+        assert_err!(
+            ProductModule::file_warranty_claim(RuntimeOrigin::signed(3), 2, evidence),
+            Error::<Test>::WarrantyExpired
+        );
+    })
+}
+
+#[test]
+fn english_auction_sells_to_highest_bid(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 4, 2000000, 0));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        //an auction without bids ends without a sale
+        assert_ok!(ProductModule::start_auction(RuntimeOrigin::signed(2), 1, AuctionKind::English, 500, 10));
+        assert_err!(
+            ProductModule::settle_auction(RuntimeOrigin::signed(3), 1),
+            Error::<Test>::AuctionNotEnded
+        );
+        System::set_block_number(11);
+        assert_ok!(ProductModule::settle_auction(RuntimeOrigin::signed(3), 1));
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 2);
+        System::assert_last_event(crate::Event::AuctionSettled{ id: 1, winner: None }.into());
+
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 900, None));
+        assert_ok!(ProductModule::start_auction(RuntimeOrigin::signed(2), 1, AuctionKind::English, 500, 10));
+        System::assert_last_event(crate::Event::AuctionStarted{ id: 1, kind: AuctionKind::English, reserve: 500, end: 21 }.into());
+        //the listing is withdrawn while the auction runs
+        assert!(!ProductModule::get_product_info(1).unwrap().get_resale());
+        assert_err!(
+            ProductModule::transfer_product(RuntimeOrigin::signed(2), 1, 3),
+            Error::<Test>::ProductInAuction
+        );
+
+        assert_err!(
+            ProductModule::bid(RuntimeOrigin::signed(2), 1, 600),
+            Error::<Test>::OwnProduct
+        );
+        assert_err!(
+            ProductModule::bid(RuntimeOrigin::signed(3), 1, 400),
+            Error::<Test>::BidTooLow
+        );
+        assert_ok!(ProductModule::bid(RuntimeOrigin::signed(3), 1, 600));
+        assert_eq!(Balances::reserved_balance(3), 600);
+        assert_err!(
+            ProductModule::bid(RuntimeOrigin::signed(4), 1, 600),
+            Error::<Test>::BidTooLow
+        );
+
+        //the outbid reserve is returned
+        assert_ok!(ProductModule::bid(RuntimeOrigin::signed(4), 1, 700));
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert_eq!(Balances::reserved_balance(4), 700);
+
+        System::set_block_number(21);
+        assert_err!(
+            ProductModule::bid(RuntimeOrigin::signed(3), 1, 800),
+            Error::<Test>::AuctionEnded
+        );
+        let seller_balance = Balances::free_balance(2);
+        ProductModule::on_finalize(21);
+
+        assert!(ProductModule::get_auction(1).is_none());
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 4);
+        assert_eq!(Balances::reserved_balance(4), 0);
+        assert_eq!(Balances::free_balance(4), 2000000 - 700);
+        assert_eq!(Balances::free_balance(1), 10970);
+        assert_eq!(Balances::free_balance(2), seller_balance + 560);
+        System::assert_last_event(crate::Event::AuctionSettled{ id: 1, winner: Some((4, 700)) }.into());
+    })
+}
+
+#[test]
+fn dutch_auction_sells_to_first_bid_at_price(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        assert_err!(
+            ProductModule::start_auction(RuntimeOrigin::signed(2), 1, AuctionKind::Dutch, 500, 0),
+            Error::<Test>::InvalidDuration
+        );
+        //the price falls from the product price of 1000 to the reserve
+        assert_ok!(ProductModule::start_auction(RuntimeOrigin::signed(2), 1, AuctionKind::Dutch, 500, 10));
+        assert_err!(
+            ProductModule::start_auction(RuntimeOrigin::signed(2), 1, AuctionKind::Dutch, 500, 10),
+            Error::<Test>::ProductInAuction
+        );
+
+        System::set_block_number(6);
+        let auction = ProductModule::get_auction(1).unwrap();
+        assert_eq!(ProductModule::dutch_price(&auction, 6), 750);
+        assert_err!(
+            ProductModule::bid(RuntimeOrigin::signed(3), 1, 700),
+            Error::<Test>::BidTooLow
+        );
+
+        //a higher bid pays the current price
+        let seller_balance = Balances::free_balance(2);
+        assert_ok!(ProductModule::bid(RuntimeOrigin::signed(3), 1, 800));
+        assert!(ProductModule::get_auction(1).is_none());
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 3);
+        assert_eq!(Balances::free_balance(3), 2000000 - 750);
+        assert_eq!(Balances::free_balance(1), 10975);
+        assert_eq!(Balances::free_balance(2), seller_balance + 600);
+        System::assert_last_event(crate::Event::AuctionSettled{ id: 1, winner: Some((3, 750)) }.into());
+
+        //nothing is left to settle at the end
+        ProductModule::on_finalize(11);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 3);
+    })
+}
+
+#[test]
+fn manufacturer_recall_refunds_owner_from_deposit(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_eq!(ProductModule::get_sale_price(1), Some(1000));
+        assert_ok!(ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 1500, None));
+
+        //the deposit only covers part of the price paid
+        assert_ok!(ProductModule::reserve_recall_deposit(RuntimeOrigin::signed(1), 600));
+        assert_eq!(Balances::reserved_balance(1), 600);
+
+        let reason = H256::repeat_byte(7);
+        assert_err!(
+            ProductModule::recall_product(RuntimeOrigin::signed(3), RecallTarget::Product(1), reason, true),
+            Error::<Test>::NotManufacturer
+        );
+
+        let owner_balance = Balances::free_balance(2);
+        assert_ok!(ProductModule::recall_product(RuntimeOrigin::signed(1), RecallTarget::Product(1), reason, true));
+        assert_eq!(Balances::free_balance(2), owner_balance + 600);
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(ProductModule::get_recall_deposit(1), 0);
+        assert_eq!(ProductModule::get_recall(1).unwrap().refunded, 600);
+        System::assert_has_event(crate::Event::RecallRefunded{ id: 1, owner: 2, amount: 600 }.into());
+        System::assert_last_event(crate::Event::ProductRecalled{ id: 1, owner: 2, reason_hash: reason }.into());
+
+        let mut p = ProductModule::get_product_info(1).unwrap();
+        assert_eq!(p.get_position(), ProductPositionEnum::Recalled);
+        assert!(!p.get_resale());
+
+        //recalled products are out of trade
+        assert_err!(
+            ProductModule::buy_product(RuntimeOrigin::signed(3), 1, 1),
+            Error::<Test>::ProductIsRecalled
+        );
+        assert_err!(
+            ProductModule::enable_resale(RuntimeOrigin::signed(2), 1, 1500, None),
+            Error::<Test>::ProductIsRecalled
+        );
+        assert_err!(
+            ProductModule::recall_product(RuntimeOrigin::signed(1), RecallTarget::Product(1), reason, true),
+            Error::<Test>::ProductIsRecalled
+        );
+    })
+}
+
+#[test]
+fn recalled_lot_can_not_be_split_back_into_trade(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), TEST_OWNER_ACCOUNT, 20000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 2, 2000000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 3, 2000000, 0));
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer));
+        assert_ok!(ProductModule::add_batch(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("bolts")).unwrap(), 10, 100, None, None));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Distribution));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Retailer));
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 100));
+
+        assert_ok!(ProductModule::recall_product(RuntimeOrigin::root(), RecallTarget::Product(1), H256::repeat_byte(5), false));
+        assert_err!(
+            ProductModule::split_batch(RuntimeOrigin::signed(2), 1, 40),
+            Error::<Test>::ProductIsRecalled
+        );
+
+        //any other split of a recalled lot keeps the recall
+        assert_ok!(ProductModule::do_split(1, &mut ProductModule::get_product_info(1).unwrap(), 40, &2));
+        assert!(ProductModule::get_recall(2).is_some());
+        assert_err!(
+            ProductModule::make_offer(RuntimeOrigin::signed(3), 2, 300, 10),
+            Error::<Test>::ProductIsRecalled
+        );
+        assert_err!(
+            ProductModule::start_auction(RuntimeOrigin::signed(2), 2, AuctionKind::English, 300, 10),
+            Error::<Test>::ProductIsRecalled
+        );
+    })
+}
+
+#[test]
+fn regulator_recalls_a_batch(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        EscrowEnabled::set(true);
+        setup_product_at_retailer();
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 1000, None, None));
+
+        //an escrowed purchase is refunded to the buyer
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_eq!(Balances::reserved_balance(2), 1000);
+
+        let reason = H256::repeat_byte(9);
+        let batch = RecallTarget::Batch(BoundedVec::try_from(vec![1, 2]).unwrap());
+        assert_ok!(ProductModule::recall_product(RuntimeOrigin::root(), batch, reason, false));
+
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert!(ProductModule::get_escrow(1).is_none());
+        assert!(ProductModule::get_recall(1).is_some());
+        assert!(ProductModule::get_recall(2).is_some());
+        assert_eq!(ProductModule::products_by_position(PositionKind::Recalled), vec![1, 2]);
+        System::assert_last_event(crate::Event::ProductRecalled{ id: 2, owner: 1, reason_hash: reason }.into());
+    })
+}
+
+#[test]
+fn buy_product_priced_in_asset(){
+    minimal_test_ext().execute_with(||{
+        setup_product_at_retailer();
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 7, 1, true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 7, 2, 5000));
+
+        //only the owner can change the listing asset
+        assert_err!(
+            ProductModule::set_price_asset(RuntimeOrigin::signed(2), 1, PaymentAsset::Asset(7)),
+            Error::<Test>::BadOrigin
+        );
+        assert_ok!(ProductModule::set_price_asset(RuntimeOrigin::signed(1), 1, PaymentAsset::Asset(7)));
+
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+
+        assert_eq!(Assets::balance(7, 2), 4000);
+        assert_eq!(Assets::balance(7, TEST_OWNER_ACCOUNT), 100);
+        assert_eq!(Assets::balance(7, 1), 900);
+        //native balance untouched
+        assert_eq!(Balances::free_balance(2), 2000000);
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 2);
+    })
+}
+
+#[test]
+fn escrow_holds_asset_in_pallet_account(){
+    minimal_test_ext().execute_with(||{
+        EscrowEnabled::set(true);
+        System::set_block_number(1);
+        setup_product_at_retailer();
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 7, 1, true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 7, 2, 5000));
+        assert_ok!(ProductModule::set_price_asset(RuntimeOrigin::signed(1), 1, PaymentAsset::Asset(7)));
+
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 1));
+        assert_eq!(Assets::balance(7, ProductModule::account_id()), 1000);
+
+        //refunded at the deadline when never shipped
+        ProductModule::on_initialize(11);
+        assert_eq!(Assets::balance(7, ProductModule::account_id()), 0);
+        assert_eq!(Assets::balance(7, 2), 5000);
+    })
+}
+
+#[test]
+fn product_deposit_reserved_and_refunded(){
+    new_test_ext().execute_with(||{
+        ProductDeposit::set(300);
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None));
+        assert_eq!(Balances::reserved_balance(1), 300);
+        assert_eq!(ProductModule::get_product_deposit(1), Some((1, 300)));
+
+        //only the owner can remove the product
+        assert_err!(
+            ProductModule::remove_product(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::BadOrigin
+        );
+
+        assert_ok!(ProductModule::remove_product(RuntimeOrigin::signed(1), 1));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert!(ProductModule::get_product_info(1).is_none());
+        assert!(ProductModule::get_product_deposit(1).is_none());
+        assert!(ProductModule::product_history(1).is_empty());
+
+        //without funds for the deposit no product can be added
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 3));
+        assert!(ProductModule::add_product(RuntimeOrigin::signed(3), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None).is_err());
+    })
+}
+
+#[test]
+fn batch_split_and_partial_purchase(){
+    minimal_test_ext().execute_with(||{
+        System::set_block_number(1);
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), TEST_OWNER_ACCOUNT, 20000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 2, 2000000, 0));
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer));
+
+        assert_err!(
+            ProductModule::add_batch(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("bolts")).unwrap(), 10, 0, None, None),
+            Error::<Test>::InvalidQuantity
+        );
+        assert_ok!(ProductModule::add_batch(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("bolts")).unwrap(), 10, 100, None, None));
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_quantity(), 100);
+
+        //split off a lot of 40 units
+        assert_ok!(ProductModule::split_batch(RuntimeOrigin::signed(1), 1, 40));
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_quantity(), 60);
+        assert_eq!(ProductModule::get_product_info(2).unwrap().get_quantity(), 40);
+        //the new lot keeps the history of the lot it came from
+        let history = ProductModule::product_history(2);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].action, ProductAction::Created);
+        assert_eq!(history[1].action, ProductAction::Split);
+        assert_err!(
+            ProductModule::split_batch(RuntimeOrigin::signed(1), 1, 60),
+            Error::<Test>::InvalidQuantity
+        );
+
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Distribution));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Retailer));
+
+        assert_err!(
+            ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 61),
+            Error::<Test>::InvalidQuantity
+        );
+
+        //buying 25 units moves them into a new product owned by the buyer
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 25));
+        let mut stock = ProductModule::get_product_info(1).unwrap();
+        assert_eq!(stock.get_quantity(), 35);
+        assert_eq!(stock.get_owner(), 1);
+        let mut bought = ProductModule::get_product_info(3).unwrap();
+        assert_eq!(bought.get_quantity(), 25);
+        assert_eq!(bought.get_owner(), 2);
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20025);
+        assert_eq!(Balances::free_balance(1), 10225);
+        System::assert_has_event(crate::Event::ProductSold{ id: 3, seller: 1, buyer: 2, price: 250, quantity: 25 }.into());
+
+        //buying the rest transfers the lot itself
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 35));
+        assert_eq!(ProductModule::get_product_info(1).unwrap().get_owner(), 2);
+        assert_eq!(Balances::free_balance(TEST_OWNER_ACCOUNT), 20060);
+        assert_eq!(Balances::free_balance(1), 10540);
+    })
+}
+
+#[test]
+fn refunded_partial_purchase_leaves_the_deposit_with_the_seller(){
+    minimal_test_ext().execute_with(||{
+        EscrowEnabled::set(true);
+        ProductDeposit::set(300);
+        System::set_block_number(1);
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 1, 10000, 0));
+        assert_ok!(Balances::set_balance(RuntimeOrigin::root(), 2, 2000000, 0));
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Retailer));
+        assert_ok!(ProductModule::add_batch(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("bolts")).unwrap(), 10, 100, None, None));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Distribution));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 1, ProductPositionEnum::Retailer));
+
+        //the split lot is held in escrow, its deposit comes from the seller
+        assert_ok!(ProductModule::buy_product(RuntimeOrigin::signed(2), 1, 25));
+        assert_eq!(ProductModule::get_product_deposit(2), Some((1, 300)));
+        assert_eq!(Balances::reserved_balance(1), 600);
+        assert_eq!(Balances::reserved_balance(2), 250);
+
+        //never shipped, the buyer gets back everything held
+        ProductModule::on_initialize(11);
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 2000000);
+        assert_eq!(ProductModule::get_product_info(2).unwrap().get_owner(), 1);
+    })
+}
+
+#[test]
+fn update_metadata_by_manufacturer(){
+    new_test_ext().execute_with(||{
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("other")).unwrap(), 4, None, None));
+
+        let mut metadata = ProductMetadata::<Test>{
+            sku: BoundedVec::try_from(Vec::from("SKU-001")).unwrap(),
+            gtin: Some(BoundedVec::try_from(Vec::from("4006381333931")).unwrap()),
+            description: BoundedVec::try_from(Vec::from("stabilo point 88")).unwrap(),
+            category: BoundedVec::try_from(Vec::from("stationery")).unwrap(),
+            content_id: Some(BoundedVec::try_from(Vec::from("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi")).unwrap()),
+        };
+
+        assert_err!(
+            ProductModule::update_metadata(RuntimeOrigin::signed(2), 1, metadata.clone()),
+            Error::<Test>::NotManufacturer
+        );
+
+        assert_ok!(ProductModule::update_metadata(RuntimeOrigin::signed(1), 1, metadata.clone()));
+        assert_eq!(ProductModule::get_product_metadata(1), Some(metadata.clone()));
+
+        //wrong check digit
+        metadata.gtin = Some(BoundedVec::try_from(Vec::from("4006381333932")).unwrap());
+        assert_err!(
+            ProductModule::update_metadata(RuntimeOrigin::signed(1), 1, metadata),
+            Error::<Test>::InvalidGtin
+        );
+    })
+}
+
+#[test]
+fn query_products_by_owner_and_position(){
+    new_test_ext().execute_with(||{
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 1));
+        assert_ok!(ProductModule::add_authorized_user(RuntimeOrigin::root(), 2));
+        assert_ok!(ProductModule::grant_role(RuntimeOrigin::root(), 1, Role::Distributor));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("a")).unwrap(), 4, None, None));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(2), BoundedVec::try_from(Vec::from("b")).unwrap(), 4, None, None));
+        assert_ok!(ProductModule::add_product(RuntimeOrigin::signed(1), BoundedVec::try_from(Vec::from("c")).unwrap(), 4, None, None));
+        assert_ok!(ProductModule::update_position(RuntimeOrigin::signed(1), 3, ProductPositionEnum::Distribution));
+
+        assert_eq!(ProductModule::products_by_owner(&1), vec![1, 3]);
+        assert_eq!(ProductModule::products_by_owner(&2), vec![2]);
+        assert_eq!(ProductModule::products_by_position(PositionKind::Manufacture), vec![1, 2]);
+        assert_eq!(ProductModule::products_by_position(PositionKind::Distribution), vec![3]);
+
+        let info = ProductModule::product_info(2).unwrap();
+        assert_eq!(info.name, b"b".to_vec());
+        assert_eq!(info.owner, 2);
+        assert!(ProductModule::product_info(4).is_none());
+    })
+}
+
+#[test]
+fn migrate_to_v1_rehashes_products_and_fills_indexes(){
+    use crate::migrations::{v1::{old_product_key, MigrateToV1, OldProduct, V1Product}, v2::MigrateToV2, v3::MigrateToV3};
+    use frame_support::{storage::unhashed, traits::{OnRuntimeUpgrade, StorageVersion, GetStorageVersion}};
+
+    new_test_ext().execute_with(||{
+        StorageVersion::new(0).put::<ProductModule>();
+
+        unhashed::put(&old_product_key::<Test>(1), &OldProduct::<Test>{
+            name: BoundedVec::try_from(Vec::from("old")).unwrap(),
+            price: 30,
+            owner: 5,
+            is_sold: true,
+            resale: true,
+            position: ProductPositionEnum::Retailer,
+        });
+        pallet_product::ProductCounter::<Test>::put(1);
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert!(unhashed::get_raw(&old_product_key::<Test>(1)).is_none());
+        let p = unhashed::get::<V1Product<Test>>(&pallet_product::Products::<Test>::hashed_key_for(1)).unwrap();
+        assert_eq!(p.owner, 5);
+        assert_eq!(p.manufacturer, 5);
+        assert_eq!(p.price, 30);
+        assert_eq!(p.quantity, 1);
+        assert!(p.is_sold && p.resale);
+        assert_eq!(ProductModule::products_by_owner(&5), vec![1]);
+        assert_eq!(ProductModule::products_by_position(PositionKind::Retailer), vec![1]);
+        assert_eq!(ProductModule::on_chain_storage_version(), 1);
+
+        //the later upgrades bring the product to the current layout
+        MigrateToV2::<Test>::on_runtime_upgrade();
+        MigrateToV3::<Test>::on_runtime_upgrade();
+        let mut p = ProductModule::get_product_info(1).unwrap();
+        assert_eq!(p.get_owner(), 5);
+        assert_eq!(p.get_price(), 30);
+        assert_eq!(p.get_purchased_at(), None);
+        assert_eq!(p.get_warranty_period(), None);
+        assert_eq!(ProductModule::on_chain_storage_version(), 3);
+    })
+}
+
+#[test]
+fn migrate_to_v1_continues_over_several_blocks(){
+    use crate::migrations::v1::{migrate_batch, old_product_key, MigrationCursor, OldProduct};
+    use frame_support::{storage::unhashed, traits::{StorageVersion, GetStorageVersion}};
+
+    new_test_ext().execute_with(||{
+        StorageVersion::new(0).put::<ProductModule>();
+
+        for (id, owner) in [(1u128, 5u64), (2, 6), (3, 7)] {
+            unhashed::put(&old_product_key::<Test>(id), &OldProduct::<Test>{
+                name: BoundedVec::try_from(Vec::from("old")).unwrap(),
+                price: 30,
+                owner,
+                is_sold: false,
+                resale: false,
+                position: ProductPositionEnum::Manufacture,
+            });
+        }
+        pallet_product::ProductCounter::<Test>::put(3);
+
+        //the first batch stops at the cursor
+        migrate_batch::<Test>(2);
+        assert_eq!(MigrationCursor::<Test>::get(), Some(3));
+        assert!(unhashed::exists(&old_product_key::<Test>(3)));
+        assert_eq!(ProductModule::products_by_position(PositionKind::Manufacture), vec![1, 2]);
+        assert_eq!(ProductModule::on_chain_storage_version(), 0);
+
+        //the next block finishes v1 and runs the later upgrades
+        ProductModule::on_initialize(1);
+        assert!(MigrationCursor::<Test>::get().is_none());
+        assert!(!unhashed::exists(&old_product_key::<Test>(3)));
+        assert_eq!(ProductModule::get_product_info(3).unwrap().get_owner(), 7);
+        assert_eq!(ProductModule::products_by_owner(&7), vec![3]);
+        assert_eq!(ProductModule::on_chain_storage_version(), 3);
+    })
+}
+
+#[test]
+fn migrate_to_v2_adds_purchase_block(){
+    use crate::migrations::{v1::V1Product, v2::{MigrateToV2, V2Product}};
+    use frame_support::{storage::unhashed, traits::{OnRuntimeUpgrade, StorageVersion, GetStorageVersion}};
+
+    new_test_ext().execute_with(||{
+        StorageVersion::new(1).put::<ProductModule>();
+
+        let key = pallet_product::Products::<Test>::hashed_key_for(1);
+        unhashed::put(&key, &V1Product::<Test>{
+            name: BoundedVec::try_from(Vec::from("old")).unwrap(),
+            price: 30,
+            quantity: 4,
+            asset: PaymentAsset::Native,
+            owner: 5,
+            manufacturer: 6,
+            is_sold: true,
+            resale: false,
+            position: ProductPositionEnum::Retailer,
+        });
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+
+        let p = unhashed::get::<V2Product<Test>>(&key).unwrap();
+        assert_eq!(p.owner, 5);
+        assert_eq!(p.manufacturer, 6);
+        assert_eq!(p.quantity, 4);
+        assert!(p.is_sold);
+        assert_eq!(p.purchased_at, None);
+        assert_eq!(ProductModule::on_chain_storage_version(), 2);
+    })
+}
+
+#[test]
+fn migrate_to_v3_adds_warranty(){
+    use crate::migrations::{v2::V2Product, v3::MigrateToV3};
+    use frame_support::{storage::unhashed, traits::{OnRuntimeUpgrade, StorageVersion, GetStorageVersion}};
+
+    new_test_ext().execute_with(||{
+        StorageVersion::new(2).put::<ProductModule>();
+
+        unhashed::put(&pallet_product::Products::<Test>::hashed_key_for(1), &V2Product::<Test>{
+            name: BoundedVec::try_from(Vec::from("old")).unwrap(),
+            price: 30,
+            quantity: 1,
+            asset: PaymentAsset::Native,
+            owner: 5,
+            manufacturer: 6,
+            is_sold: true,
+            resale: false,
+            position: ProductPositionEnum::Retailer,
+            purchased_at: Some(7),
+        });
+
+        MigrateToV3::<Test>::on_runtime_upgrade();
+
+        let mut p = ProductModule::get_product_info(1).unwrap();
+        assert_eq!(p.get_owner(), 5);
+        assert_eq!(p.get_purchased_at(), Some(7));
+        assert_eq!(p.get_warranty_period(), None);
+        assert_eq!(p.get_warranty_expiry(), None);
+        assert_eq!(ProductModule::on_chain_storage_version(), 3);
+    })
+}
@@ -0,0 +1,36 @@
+use crate::types::ProductPositionEnum;
+
+
+// decides which position changes `update_position` accepts
+pub trait TransitionRules {
+    fn can_transition(from: ProductPositionEnum, to: ProductPositionEnum)-> bool;
+}
+
+
+// Manufacture -> Distribution -> Retailer, with optional transit and warehouse legs in between,
+// returns from the retailer back into distribution, and recall or destruction from any
+// non terminal position
+pub struct DefaultTransitionRules;
+
+impl TransitionRules for DefaultTransitionRules {
+    fn can_transition(from: ProductPositionEnum, to: ProductPositionEnum)-> bool{
+        use ProductPositionEnum::*;
+
+        if from.is_terminal() {
+            return false;
+        }
+
+        matches!(
+            (from, to),
+            (Manufacture, Distribution) |
+            (Distribution, Retailer) |
+            (Manufacture | Distribution | Warehouse { .. }, InTransit { .. }) |
+            (InTransit { .. }, Distribution | Warehouse { .. } | Retailer) |
+            (Distribution, Warehouse { .. }) |
+            (Warehouse { .. }, Distribution) |
+            (Retailer, Returned) |
+            (Returned, Distribution | Warehouse { .. }) |
+            (_, Recalled | Destroyed)
+        )
+    }
+}
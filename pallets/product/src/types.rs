@@ -3,40 +3,198 @@ use crate as product;
 use frame_support::{BoundedVec};
 use scale_info::TypeInfo;
 use product::pallet::Config;
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, Get};
 use codec::{MaxEncodedLen, Encode, Decode};
 use sp_core::ConstU32;
+use sp_runtime::{traits::Saturating, Perbill};
+use sp_std::{marker::PhantomData, vec::Vec};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 
-pub type ProductName = BoundedVec<u8, ConstU32<10>>;
+pub type ProductName<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
+pub type ProductDescription<T> = BoundedVec<u8, <T as Config>::MaxDescriptionLength>;
+
+pub type Sku = BoundedVec<u8, ConstU32<32>>;
+
+pub type Category = BoundedVec<u8, ConstU32<32>>;
+
+// GS1 GTIN-8, GTIN-12 (UPC), GTIN-13 (EAN) or GTIN-14 as ascii digits
+pub type Gtin = BoundedVec<u8, ConstU32<14>>;
+
+// CID or content hash of the off-chain product document
+pub type ContentId = BoundedVec<u8, ConstU32<64>>;
+
+pub type CarrierId = u32;
+
+pub type FacilityId = u32;
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
 
+pub type AssetIdOf<T> = <T as Config>::AssetId;
+
+// accounts and their share of the first sale of a product
+pub type PayoutSplits<T> = BoundedVec<(AccountIdOf<T>, Perbill), <T as Config>::MaxSplits>;
 
+
+// what a product is priced and paid in
 #[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen,Debug,Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PaymentAsset<AssetId> {
+    Native,
+    Asset(AssetId),
+}
+
+
+// new positions are only ever appended so stored products keep decoding
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen,Debug,Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ProductPositionEnum {
+    #[codec(index = 0)]
+    Manufacture,
+    #[codec(index = 1)]
+    Distribution,
+    #[codec(index = 2)]
+    Retailer,
+    #[codec(index = 3)]
+    InTransit { carrier: CarrierId },
+    #[codec(index = 4)]
+    Warehouse { facility_id: FacilityId },
+    #[codec(index = 5)]
+    Returned,
+    #[codec(index = 6)]
+    Recalled,
+    #[codec(index = 7)]
+    Destroyed,
+}
+
+impl ProductPositionEnum {
+    // role an account needs to move a product into this position
+    pub fn required_role(&self)-> Role{
+        match self {
+            ProductPositionEnum::Manufacture => Role::Manufacturer,
+            ProductPositionEnum::Distribution => Role::Distributor,
+            ProductPositionEnum::Retailer => Role::Retailer,
+            ProductPositionEnum::InTransit { .. } => Role::Distributor,
+            ProductPositionEnum::Warehouse { .. } => Role::Distributor,
+            ProductPositionEnum::Returned => Role::Retailer,
+            ProductPositionEnum::Recalled => Role::Manufacturer,
+            ProductPositionEnum::Destroyed => Role::Manufacturer,
+        }
+    }
+
+    // no position change is possible out of a terminal position
+    pub fn is_terminal(&self)-> bool{
+        matches!(self, ProductPositionEnum::Recalled | ProductPositionEnum::Destroyed)
+    }
+
+    pub fn kind(&self)-> PositionKind{
+        match self {
+            ProductPositionEnum::Manufacture => PositionKind::Manufacture,
+            ProductPositionEnum::Distribution => PositionKind::Distribution,
+            ProductPositionEnum::Retailer => PositionKind::Retailer,
+            ProductPositionEnum::InTransit { .. } => PositionKind::InTransit,
+            ProductPositionEnum::Warehouse { .. } => PositionKind::Warehouse,
+            ProductPositionEnum::Returned => PositionKind::Returned,
+            ProductPositionEnum::Recalled => PositionKind::Recalled,
+            ProductPositionEnum::Destroyed => PositionKind::Destroyed,
+        }
+    }
+}
+
+
+// a position without its carrier or facility, the position index lists every product in
+// transit or in a warehouse under one key
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen,Debug,Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PositionKind {
+    #[codec(index = 0)]
     Manufacture,
+    #[codec(index = 1)]
     Distribution,
+    #[codec(index = 2)]
     Retailer,
+    #[codec(index = 3)]
+    InTransit,
+    #[codec(index = 4)]
+    Warehouse,
+    #[codec(index = 5)]
+    Returned,
+    #[codec(index = 6)]
+    Recalled,
+    #[codec(index = 7)]
+    Destroyed,
 }
 
 
+// roles an authorized account can hold in the supply chain
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen,Debug,Copy)]
+pub enum Role {
+    Manufacturer,
+    Distributor,
+    Retailer,
+    Auditor,
+    Admin,
+}
+
+impl Role {
+    pub fn bit(&self)-> u8{
+        match self {
+            Role::Manufacturer => 1 << 0,
+            Role::Distributor => 1 << 1,
+            Role::Retailer => 1 << 2,
+            Role::Auditor => 1 << 3,
+            Role::Admin => 1 << 4,
+        }
+    }
+}
+
+
+// bitflags of the roles held by one account
+// the first bit is `Manufacturer` so entries of the old boolean map decode as manufacturers
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen,Debug,Copy,Default)]
+pub struct Roles(u8);
+
+impl Roles {
+    pub fn contains(&self, role: Role)->bool{ self.0 & role.bit() != 0 }
+    pub fn insert(&mut self, role: Role){ self.0 |= role.bit() }
+    pub fn remove(&mut self, role: Role){ self.0 &= !role.bit() }
+    pub fn is_empty(&self)->bool{ self.0 == 0 }
+}
+
+impl From<Role> for Roles {
+    fn from(role: Role)-> Self{ Roles(role.bit()) }
+}
+
+
+// stored in `Products`, any layout change needs a migration in `migrations.rs` and a
+// `STORAGE_VERSION` bump
 #[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
 #[codec(mel_bound())]
 pub struct Product<T:Config>{
 
     // The name of the product
-    name:ProductName,
+    name:ProductName<T>,
     
-    // The price of the product
+    // The price of one unit of the product
     price:BalanceOf<T>,
 
-    // The ID of the user who added the product
+    // number of identical units in this lot
+    quantity: u32,
+
+    // The asset the price is denominated in
+    asset: PaymentAsset<AssetIdOf<T>>,
+
+    // The ID of the current owner of the product
     owner: T::AccountId,
 
+    // The ID of the user who added the product, receives resale royalties
+    manufacturer: T::AccountId,
+
     // whether product is sold or not 
     is_sold: bool,
 
@@ -45,18 +203,33 @@ pub struct Product<T:Config>{
 
     //destination
     position: ProductPositionEnum,
+
+    // block of the last purchase, the return period runs from here
+    purchased_at: Option<T::BlockNumber>,
+
+    // blocks the manufacturer warranty lasts after the first sale
+    warranty_period: Option<T::BlockNumber>,
+
+    // last block claims can be filed, set by the first sale
+    warranty_expiry: Option<T::BlockNumber>,
     
 }
 
 impl<T:Config> Product<T> {
-    pub fn new(name: ProductName, price:BalanceOf<T>, owner: T::AccountId,  position: ProductPositionEnum)-> Self{
+    pub fn new(name: ProductName<T>, price:BalanceOf<T>, owner: T::AccountId,  position: ProductPositionEnum)-> Self{
         Product::<T>{
             name,
             price,
+            quantity: 1,
+            asset: PaymentAsset::Native,
+            manufacturer: owner.clone(),
             owner,
             position,
             is_sold:false,
-            resale:false
+            resale:false,
+            purchased_at: None,
+            warranty_period: None,
+            warranty_expiry: None,
         }
     }
 
@@ -66,14 +239,68 @@ impl<T:Config> Product<T> {
     pub fn get_owner(&mut self)->T::AccountId{return self.owner.clone()}
     pub fn set_owner(&mut self, owner: T::AccountId){self.owner= owner}
 
+    pub fn get_manufacturer(&mut self)->T::AccountId{return self.manufacturer.clone()}
+
     pub fn get_is_sold(&mut self)->bool{ return self.is_sold}
     pub fn set_is_sold(&mut self, sold:bool){self.is_sold=sold}
 
     pub fn get_price(&mut self)->BalanceOf<T>{return self.price}
     pub fn set_price(&mut self, price: BalanceOf<T>){self.price= price}
 
+    pub fn get_quantity(&mut self)->u32{return self.quantity}
+    pub fn set_quantity(&mut self, quantity: u32){self.quantity= quantity}
+
+    // price of the whole lot
+    pub fn get_total_price(&mut self)->BalanceOf<T>{ self.price.saturating_mul(self.quantity.into()) }
+
+    pub fn get_asset(&mut self)->PaymentAsset<AssetIdOf<T>>{return self.asset}
+    pub fn set_asset(&mut self, asset: PaymentAsset<AssetIdOf<T>>){self.asset= asset}
+
+    // keys of the owner and position indexes
+    pub fn index_keys(&self)-> (T::AccountId, PositionKind){ (self.owner.clone(), self.position.kind()) }
+
     pub fn get_resale(&mut self)->bool{return self.resale}
     pub fn set_resale(&mut self, resale:bool){self.resale= resale}
+
+    pub fn get_purchased_at(&mut self)->Option<T::BlockNumber>{return self.purchased_at}
+    pub fn set_purchased_at(&mut self, purchased_at: Option<T::BlockNumber>){self.purchased_at= purchased_at}
+
+    pub fn get_warranty_period(&mut self)->Option<T::BlockNumber>{return self.warranty_period}
+    pub fn set_warranty_period(&mut self, period: Option<T::BlockNumber>){self.warranty_period= period}
+
+    pub fn get_warranty_expiry(&mut self)->Option<T::BlockNumber>{return self.warranty_expiry}
+    pub fn set_warranty_expiry(&mut self, expiry: Option<T::BlockNumber>){self.warranty_expiry= expiry}
+}
+
+// plain view of a product served by the runtime api
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProductInfo<AccountId, Balance, AssetId> {
+    pub name: Vec<u8>,
+    pub price: Balance,
+    pub quantity: u32,
+    pub asset: PaymentAsset<AssetId>,
+    pub owner: AccountId,
+    pub manufacturer: AccountId,
+    pub is_sold: bool,
+    pub resale: bool,
+    pub position: ProductPositionEnum,
+}
+
+impl<T: Config> From<Product<T>> for ProductInfo<AccountIdOf<T>, BalanceOf<T>, AssetIdOf<T>> {
+    fn from(p: Product<T>)-> Self{
+        ProductInfo{
+            name: p.name.into_inner(),
+            price: p.price,
+            quantity: p.quantity,
+            asset: p.asset,
+            owner: p.owner,
+            manufacturer: p.manufacturer,
+            is_sold: p.is_sold,
+            resale: p.resale,
+            position: p.position,
+        }
+    }
 }
 
 impl<T: Config> core::fmt::Debug for Product<T> {
@@ -82,6 +309,364 @@ impl<T: Config> core::fmt::Debug for Product<T> {
 			.field("name", &self.name)
 			.field("owner", &self.owner)
 			.field("price", &self.price)
+			.field("quantity", &self.quantity)
+			.field("asset", &self.asset)
 			.finish()
 	}
-}
\ No newline at end of file
+}
+
+
+// what happened to a product in a history entry
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen,Debug,Copy)]
+pub enum ProductAction {
+    Created,
+    PositionUpdated,
+    Sold,
+    ResaleEnabled,
+    Split,
+    ResaleDisabled,
+    ResalePriceUpdated,
+    ResaleExpired,
+    Transferred,
+    Recalled,
+    Returned,
+    WarrantyClaimed,
+    ClaimResolved,
+}
+
+
+// one mutation of a product, recorded for the chain of custody
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct ProductHistoryEntry<T:Config>{
+
+    pub block_number: T::BlockNumber,
+
+    // account which caused the change
+    pub actor: T::AccountId,
+
+    pub action: ProductAction,
+
+    // product state right after the change
+    pub position: ProductPositionEnum,
+
+    pub owner: T::AccountId,
+
+    pub price: BalanceOf<T>,
+}
+
+impl<T:Config> ProductHistoryEntry<T> {
+    pub fn new(block_number: T::BlockNumber, actor: T::AccountId, action: ProductAction, product: &Product<T>)-> Self{
+        ProductHistoryEntry::<T>{
+            block_number,
+            actor,
+            action,
+            position: product.position,
+            owner: product.owner.clone(),
+            price: product.price,
+        }
+    }
+}
+
+impl<T: Config> core::fmt::Debug for ProductHistoryEntry<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("ProductHistoryEntry")
+			.field("block_number", &self.block_number)
+			.field("actor", &self.actor)
+			.field("action", &self.action)
+			.field("position", &self.position)
+			.field("owner", &self.owner)
+			.field("price", &self.price)
+			.finish()
+	}
+}
+
+
+// bound of the payouts of one sale: the fee, the royalty, every split and the seller
+pub struct MaxPayouts<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for MaxPayouts<T> {
+    fn get()-> u32{ T::MaxSplits::get().saturating_add(3) }
+}
+
+// who was paid what for the last sale of a product, paid back when a return is approved
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct SaleInfo<T:Config>{
+
+    pub buyer: T::AccountId,
+
+    pub seller: T::AccountId,
+
+    pub asset: PaymentAsset<AssetIdOf<T>>,
+
+    pub payouts: BoundedVec<(T::AccountId, BalanceOf<T>), MaxPayouts<T>>,
+
+    // a returned first sale makes the product unsold again
+    pub first_sale: bool,
+}
+
+impl<T: Config> core::fmt::Debug for SaleInfo<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("SaleInfo")
+			.field("buyer", &self.buyer)
+			.field("seller", &self.seller)
+			.field("asset", &self.asset)
+			.field("payouts", &self.payouts)
+			.field("first_sale", &self.first_sale)
+			.finish()
+	}
+}
+
+
+// purchase price held in escrow until the buyer confirms delivery
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct EscrowInfo<T:Config>{
+
+    pub buyer: T::AccountId,
+
+    // account which ships the product
+    pub seller: T::AccountId,
+
+    pub amount: BalanceOf<T>,
+
+    pub asset: PaymentAsset<AssetIdOf<T>>,
+
+    // block at which the escrow is settled automatically
+    pub deadline: T::BlockNumber,
+
+    pub shipped: bool,
+}
+
+impl<T: Config> core::fmt::Debug for EscrowInfo<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("EscrowInfo")
+			.field("buyer", &self.buyer)
+			.field("seller", &self.seller)
+			.field("amount", &self.amount)
+			.field("deadline", &self.deadline)
+			.field("shipped", &self.shipped)
+			.finish()
+	}
+}
+
+
+// amount offered for a product, held from the bidder until the offer is settled
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct OfferInfo<T:Config>{
+
+    pub amount: BalanceOf<T>,
+
+    // asset the amount is held in, the product price asset when the offer was made
+    pub asset: PaymentAsset<AssetIdOf<T>>,
+
+    // block at which the offer expires and the amount is returned
+    pub expiry: T::BlockNumber,
+}
+
+impl<T: Config> core::fmt::Debug for OfferInfo<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("OfferInfo")
+			.field("amount", &self.amount)
+			.field("asset", &self.asset)
+			.field("expiry", &self.expiry)
+			.finish()
+	}
+}
+
+
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen,Debug,Copy)]
+pub enum AuctionKind {
+    // ascending bids, the highest bid wins at the end
+    English,
+    // descending price, the first bid at the current price wins
+    Dutch,
+}
+
+
+// a running auction of a product, bids are reserved from the bidder
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct AuctionInfo<T:Config>{
+
+    pub kind: AuctionKind,
+
+    // lowest price the product is sold at
+    pub reserve: BalanceOf<T>,
+
+    // price a Dutch auction starts from
+    pub start_price: BalanceOf<T>,
+
+    pub start: T::BlockNumber,
+
+    // block at the end of which the auction is settled
+    pub end: T::BlockNumber,
+
+    // highest English bid and its bidder
+    pub best_bid: Option<(T::AccountId, BalanceOf<T>)>,
+}
+
+impl<T: Config> core::fmt::Debug for AuctionInfo<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("AuctionInfo")
+			.field("kind", &self.kind)
+			.field("reserve", &self.reserve)
+			.field("start_price", &self.start_price)
+			.field("start", &self.start)
+			.field("end", &self.end)
+			.field("best_bid", &self.best_bid)
+			.finish()
+	}
+}
+
+
+// products covered by one recall
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub enum RecallTarget<T:Config> {
+    Product(u128),
+    Batch(BoundedVec<u128, <T as Config>::MaxRecallBatch>),
+}
+
+impl<T:Config> RecallTarget<T> {
+    pub fn ids(&self)-> Vec<u128>{
+        match self {
+            RecallTarget::Product(id) => sp_std::vec![*id],
+            RecallTarget::Batch(ids) => ids.to_vec(),
+        }
+    }
+
+    pub fn count(&self)-> u32{
+        match self {
+            RecallTarget::Product(_) => 1,
+            RecallTarget::Batch(ids) => ids.len() as u32,
+        }
+    }
+}
+
+impl<T: Config> core::fmt::Debug for RecallTarget<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			RecallTarget::Product(id) => f.debug_tuple("Product").field(id).finish(),
+			RecallTarget::Batch(ids) => f.debug_tuple("Batch").field(ids).finish(),
+		}
+	}
+}
+
+
+// why and when a product was recalled
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct RecallInfo<T:Config>{
+
+    // hash of the off-chain recall notice
+    pub reason_hash: T::Hash,
+
+    pub block_number: T::BlockNumber,
+
+    // paid to the owner out of the manufacturer's recall deposit
+    pub refunded: BalanceOf<T>,
+}
+
+impl<T: Config> core::fmt::Debug for RecallInfo<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("RecallInfo")
+			.field("reason_hash", &self.reason_hash)
+			.field("block_number", &self.block_number)
+			.field("refunded", &self.refunded)
+			.finish()
+	}
+}
+
+
+// how the manufacturer settled a warranty claim
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen,Debug,Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ClaimOutcome {
+    Repair,
+    Replace,
+    Refund,
+}
+
+
+// a warranty claim filed by the owner of a product
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct ClaimInfo<T:Config>{
+
+    pub claimant: T::AccountId,
+
+    // hash of the off-chain evidence of the defect
+    pub evidence_hash: T::Hash,
+
+    pub filed_at: T::BlockNumber,
+
+    // set once the manufacturer resolves the claim
+    pub outcome: Option<ClaimOutcome>,
+}
+
+impl<T: Config> core::fmt::Debug for ClaimInfo<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("ClaimInfo")
+			.field("claimant", &self.claimant)
+			.field("evidence_hash", &self.evidence_hash)
+			.field("filed_at", &self.filed_at)
+			.field("outcome", &self.outcome)
+			.finish()
+	}
+}
+
+
+// descriptive data of a product, maintained by its manufacturer
+#[derive(Encode, Decode, Eq, PartialEq, Clone, TypeInfo,MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct ProductMetadata<T:Config>{
+
+    pub sku: Sku,
+
+    pub gtin: Option<Gtin>,
+
+    pub description: ProductDescription<T>,
+
+    pub category: Category,
+
+    pub content_id: Option<ContentId>,
+}
+
+impl<T: Config> core::fmt::Debug for ProductMetadata<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("ProductMetadata")
+			.field("sku", &self.sku)
+			.field("gtin", &self.gtin)
+			.field("category", &self.category)
+			.field("content_id", &self.content_id)
+			.finish()
+	}
+}
+
+
+// checks length, digits and the GS1 check digit of a GTIN
+pub fn is_valid_gtin(code: &[u8])-> bool{
+    if ![8, 12, 13, 14].contains(&code.len()) || !code.iter().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let (body, check) = code.split_at(code.len() - 1);
+    // weights alternate 3, 1, 3, ... starting from the digit next to the check digit
+    let sum: u32 = body.iter().rev().enumerate()
+        .map(|(i, c)| (c - b'0') as u32 * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+
+    (10 - sum % 10) % 10 == (check[0] - b'0') as u32
+}
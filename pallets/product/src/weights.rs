@@ -0,0 +1,699 @@
+//! Placeholder weights for pallet_product
+//!
+//! These are not benchmark results and not final weights. Each figure is estimated by hand
+//! from the storage accesses listed above it, which are counted from the code paths the
+//! benchmarks in `benchmarking.rs` exercise. The benchmarks have not been run yet. Replace the
+//! whole file with their output on reference hardware before relying on these weights:
+//!
+//! ./target/release/node-template benchmark pallet \
+//!     --chain=dev \
+//!     --steps=50 \
+//!     --repeat=20 \
+//!     --pallet=pallet_product \
+//!     --extrinsic=* \
+//!     --execution=wasm \
+//!     --wasm-execution=compiled \
+//!     --output=pallets/product/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_product.
+pub trait WeightInfo {
+	fn add_authorized_user() -> Weight;
+	fn add_product() -> Weight;
+	fn add_batch() -> Weight;
+	fn split_batch() -> Weight;
+	fn update_position() -> Weight;
+	fn buy_product() -> Weight;
+	fn enable_resale() -> Weight;
+	fn disable_resale() -> Weight;
+	fn update_resale_price() -> Weight;
+	fn grant_role() -> Weight;
+	fn revoke_role() -> Weight;
+	fn remove_authorized_user() -> Weight;
+	fn suspend_authorized_user() -> Weight;
+	fn mark_shipped() -> Weight;
+	fn confirm_delivery() -> Weight;
+	fn remove_product() -> Weight;
+	fn update_metadata() -> Weight;
+	fn set_price_asset() -> Weight;
+	fn transfer_product() -> Weight;
+	fn propose_transfer() -> Weight;
+	fn accept_transfer() -> Weight;
+	fn cancel_transfer() -> Weight;
+	fn make_offer() -> Weight;
+	fn accept_offer() -> Weight;
+	fn reject_offer() -> Weight;
+	fn start_auction() -> Weight;
+	fn bid_english() -> Weight;
+	fn bid_dutch() -> Weight;
+	fn settle_auction() -> Weight;
+	fn recall_product(n: u32, ) -> Weight;
+	fn reserve_recall_deposit() -> Weight;
+	fn release_recall_deposit() -> Weight;
+	fn set_product_owner_account() -> Weight;
+	fn request_return() -> Weight;
+	fn approve_return() -> Weight;
+	fn reject_return() -> Weight;
+	fn file_warranty_claim() -> Weight;
+	fn resolve_claim() -> Weight;
+}
+
+/// Hand-estimated placeholder weights for pallet_product.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ProductModule AuthorizedUsers (r:1 w:1)
+	fn add_authorized_user() -> Weight {
+		Weight::from_ref_time(18_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule AuthorizedUsers (r:1 w:0)
+	// Storage: ProductModule SuspendedUsers (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ProductModule ProductCounter (r:1 w:1)
+	// Storage: ProductModule Products (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:1)
+	// Storage: ProductModule ProductsByPosition (r:0 w:1)
+	// Storage: ProductModule ProductDeposits (r:0 w:1)
+	// Storage: ProductModule ProductSplits (r:0 w:1)
+	fn add_product() -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	// Storage: ProductModule AuthorizedUsers (r:1 w:0)
+	// Storage: ProductModule SuspendedUsers (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ProductModule ProductCounter (r:1 w:1)
+	// Storage: ProductModule Products (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:1)
+	// Storage: ProductModule ProductsByPosition (r:0 w:1)
+	// Storage: ProductModule ProductDeposits (r:0 w:1)
+	// Storage: ProductModule ProductSplits (r:0 w:1)
+	fn add_batch() -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	// Storage: ProductModule Products (r:3 w:2)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ProductModule ProductCounter (r:1 w:1)
+	// Storage: ProductModule ProductMetadataOf (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:2 w:2)
+	// Storage: ProductModule ProductsByOwner (r:0 w:3)
+	// Storage: ProductModule ProductsByPosition (r:0 w:3)
+	// Storage: ProductModule ProductDeposits (r:0 w:1)
+	// Storage: ProductModule ProductSplits (r:1 w:1)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	// Storage: ProductModule LastSales (r:0 w:1)
+	fn split_batch() -> Weight {
+		Weight::from_ref_time(78_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(16 as u64))
+	}
+	// Storage: ProductModule AuthorizedUsers (r:1 w:0)
+	// Storage: ProductModule SuspendedUsers (r:1 w:0)
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	fn update_position() -> Weight {
+		Weight::from_ref_time(48_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: ProductModule Products (r:4 w:2)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ProductOwnerAccount (r:1 w:0)
+	// Storage: System Account (r:8 w:8)
+	// Storage: ProductModule ProductCounter (r:1 w:1)
+	// Storage: ProductModule ProductMetadataOf (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:3 w:3)
+	// Storage: ProductModule ProductsByOwner (r:0 w:5)
+	// Storage: ProductModule ProductsByPosition (r:0 w:5)
+	// Storage: ProductModule ProductDeposits (r:0 w:1)
+	// Storage: ProductModule ProductSplits (r:1 w:1)
+	// Storage: ProductModule LastSales (r:0 w:1)
+	fn buy_product() -> Weight {
+		Weight::from_ref_time(165_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(20 as u64))
+			.saturating_add(T::DbWeight::get().writes(28 as u64))
+	}
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule ResaleDeadlines (r:2 w:2)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	fn enable_resale() -> Weight {
+		Weight::from_ref_time(52_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule ResaleDeadlines (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	fn disable_resale() -> Weight {
+		Weight::from_ref_time(46_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	fn update_resale_price() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: ProductModule AuthorizedUsers (r:1 w:1)
+	fn grant_role() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule AuthorizedUsers (r:1 w:1)
+	fn revoke_role() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule AuthorizedUsers (r:1 w:1)
+	// Storage: ProductModule SuspendedUsers (r:0 w:1)
+	fn remove_authorized_user() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: ProductModule AuthorizedUsers (r:1 w:0)
+	// Storage: ProductModule SuspendedUsers (r:0 w:1)
+	fn suspend_authorized_user() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule Escrows (r:1 w:1)
+	fn mark_shipped() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule Escrows (r:1 w:1)
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule ProductOwnerAccount (r:1 w:0)
+	// Storage: System Account (r:8 w:8)
+	// Storage: ProductModule EscrowDeadlines (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	// Storage: ProductModule ProductSplits (r:1 w:0)
+	// Storage: ProductModule LastSales (r:0 w:1)
+	fn confirm_delivery() -> Weight {
+		Weight::from_ref_time(110_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(15 as u64))
+			.saturating_add(T::DbWeight::get().writes(17 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ProductDeposits (r:1 w:1)
+	// Storage: System Account (r:21 w:21)
+	// Storage: ProductModule ProductHistory (r:0 w:1)
+	// Storage: ProductModule ProductMetadataOf (r:0 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:1)
+	// Storage: ProductModule ProductsByPosition (r:0 w:1)
+	// Storage: ProductModule ProductSplits (r:0 w:1)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	// Storage: ProductModule LastSales (r:0 w:1)
+	// Storage: ProductModule WarrantyClaims (r:0 w:10)
+	// Storage: ProductModule ClaimCounts (r:0 w:1)
+	// Storage: ProductModule Offers (r:21 w:20)
+	// Storage: ProductModule OfferDeadlines (r:20 w:20)
+	// Storage: ProductModule OfferCounts (r:20 w:20)
+	fn remove_product() -> Weight {
+		Weight::from_ref_time(412_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(106 as u64))
+			.saturating_add(T::DbWeight::get().writes(101 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:0)
+	// Storage: ProductModule ProductMetadataOf (r:0 w:1)
+	fn update_metadata() -> Weight {
+		Weight::from_ref_time(24_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	fn set_price_asset() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule ResaleDeadlines (r:1 w:1)
+	// Storage: ProductModule PendingTransfers (r:0 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	fn transfer_product() -> Weight {
+		Weight::from_ref_time(48_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:0)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule PendingTransfers (r:0 w:1)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	fn propose_transfer() -> Weight {
+		Weight::from_ref_time(22_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule PendingTransfers (r:1 w:1)
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule ResaleDeadlines (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	fn accept_transfer() -> Weight {
+		Weight::from_ref_time(50_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:0)
+	// Storage: ProductModule PendingTransfers (r:1 w:1)
+	fn cancel_transfer() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:0)
+	// Storage: ProductModule Offers (r:1 w:1)
+	// Storage: ProductModule OfferDeadlines (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn make_offer() -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule Offers (r:1 w:1)
+	// Storage: ProductModule OfferDeadlines (r:1 w:1)
+	// Storage: System Account (r:4 w:4)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule ResaleDeadlines (r:1 w:1)
+	// Storage: ProductModule PendingTransfers (r:0 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	// Storage: ProductModule ProductOwnerAccount (r:1 w:0)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	// Storage: ProductModule LastSales (r:0 w:1)
+	fn accept_offer() -> Weight {
+		Weight::from_ref_time(96_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(17 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:0)
+	// Storage: ProductModule Offers (r:1 w:1)
+	// Storage: ProductModule OfferDeadlines (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_offer() -> Weight {
+		Weight::from_ref_time(36_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule Auctions (r:1 w:1)
+	// Storage: ProductModule AuctionEnds (r:1 w:1)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule ResaleDeadlines (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	// Storage: ProductModule ReturnRequests (r:1 w:0)
+	fn start_auction() -> Weight {
+		Weight::from_ref_time(56_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	// Storage: ProductModule Auctions (r:1 w:1)
+	// Storage: ProductModule Products (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn bid_english() -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: ProductModule Auctions (r:1 w:1)
+	// Storage: ProductModule AuctionEnds (r:1 w:1)
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule ProductOwnerAccount (r:1 w:0)
+	// Storage: System Account (r:4 w:4)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule PendingTransfers (r:0 w:1)
+	// Storage: ProductModule SalePrices (r:0 w:1)
+	// Storage: ProductModule LastSales (r:0 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	fn bid_dutch() -> Weight {
+		Weight::from_ref_time(96_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(16 as u64))
+	}
+	// Storage: ProductModule Auctions (r:1 w:1)
+	// Storage: ProductModule AuctionEnds (r:1 w:1)
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: System Account (r:4 w:4)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule PendingTransfers (r:0 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	// Storage: ProductModule ProductOwnerAccount (r:1 w:0)
+	// Storage: ProductModule LastSales (r:0 w:1)
+	fn settle_auction() -> Weight {
+		Weight::from_ref_time(90_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(15 as u64))
+	}
+	// Storage: ProductModule Products (r:2 w:1)
+	// Storage: ProductModule Recalls (r:1 w:1)
+	// Storage: ProductModule Escrows (r:1 w:1)
+	// Storage: ProductModule EscrowDeadlines (r:1 w:1)
+	// Storage: ProductModule Auctions (r:1 w:0)
+	// Storage: ProductModule SalePrices (r:1 w:0)
+	// Storage: ProductModule RecallDeposits (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: ProductModule ReturnRequests (r:1 w:1)
+	// Storage: ProductModule LastSales (r:0 w:1)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	fn recall_product(n: u32, ) -> Weight {
+		Weight::from_ref_time(12_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(96_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads((14 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((16 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: ProductModule AuthorizedUsers (r:1 w:0)
+	// Storage: ProductModule SuspendedUsers (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ProductModule RecallDeposits (r:1 w:1)
+	fn reserve_recall_deposit() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: ProductModule RecallDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn release_recall_deposit() -> Weight {
+		Weight::from_ref_time(26_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: ProductModule ProductOwnerAccount (r:0 w:1)
+	fn set_product_owner_account() -> Weight {
+		Weight::from_ref_time(14_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:1)
+	// Storage: ProductModule LastSales (r:1 w:0)
+	// Storage: ProductModule ReturnRequests (r:1 w:1)
+	// Storage: ProductModule Recalls (r:1 w:0)
+	// Storage: ProductModule Escrows (r:1 w:0)
+	// Storage: ProductModule Auctions (r:1 w:0)
+	// Storage: ProductModule ResaleExpiries (r:1 w:1)
+	// Storage: ProductModule ResaleDeadlines (r:1 w:1)
+	// Storage: ProductModule PendingTransfers (r:0 w:1)
+	// Storage: ProductModule ReturnDeadlines (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	fn request_return() -> Weight {
+		Weight::from_ref_time(52_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	// Storage: ProductModule ReturnRequests (r:1 w:1)
+	// Storage: ProductModule ReturnDeadlines (r:1 w:1)
+	// Storage: ProductModule LastSales (r:1 w:1)
+	// Storage: ProductModule Products (r:1 w:1)
+	// Storage: System Account (r:9 w:9)
+	// Storage: ProductModule SalePrices (r:0 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	// Storage: ProductModule ProductsByOwner (r:0 w:2)
+	// Storage: ProductModule ProductsByPosition (r:0 w:2)
+	fn approve_return() -> Weight {
+		Weight::from_ref_time(154_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(19 as u64))
+	}
+	// Storage: ProductModule ReturnRequests (r:1 w:1)
+	// Storage: ProductModule ReturnDeadlines (r:1 w:1)
+	// Storage: ProductModule LastSales (r:1 w:1)
+	fn reject_return() -> Weight {
+		Weight::from_ref_time(26_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:0)
+	// Storage: ProductModule ClaimCounts (r:1 w:1)
+	// Storage: ProductModule WarrantyClaims (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	fn file_warranty_claim() -> Weight {
+		Weight::from_ref_time(34_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: ProductModule Products (r:1 w:0)
+	// Storage: ProductModule WarrantyClaims (r:1 w:1)
+	// Storage: ProductModule ProductHistory (r:1 w:1)
+	fn resolve_claim() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn add_authorized_user() -> Weight {
+		Weight::from_ref_time(18_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn add_product() -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	fn add_batch() -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	fn split_batch() -> Weight {
+		Weight::from_ref_time(78_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(16 as u64))
+	}
+	fn update_position() -> Weight {
+		Weight::from_ref_time(48_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	fn buy_product() -> Weight {
+		Weight::from_ref_time(165_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(20 as u64))
+			.saturating_add(RocksDbWeight::get().writes(28 as u64))
+	}
+	fn enable_resale() -> Weight {
+		Weight::from_ref_time(52_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	fn disable_resale() -> Weight {
+		Weight::from_ref_time(46_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	fn update_resale_price() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	fn grant_role() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn revoke_role() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn remove_authorized_user() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn suspend_authorized_user() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn mark_shipped() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn confirm_delivery() -> Weight {
+		Weight::from_ref_time(110_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(15 as u64))
+			.saturating_add(RocksDbWeight::get().writes(17 as u64))
+	}
+	fn remove_product() -> Weight {
+		Weight::from_ref_time(412_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(106 as u64))
+			.saturating_add(RocksDbWeight::get().writes(101 as u64))
+	}
+	fn update_metadata() -> Weight {
+		Weight::from_ref_time(24_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_price_asset() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn transfer_product() -> Weight {
+		Weight::from_ref_time(48_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	fn propose_transfer() -> Weight {
+		Weight::from_ref_time(22_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn accept_transfer() -> Weight {
+		Weight::from_ref_time(50_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	fn cancel_transfer() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn make_offer() -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn accept_offer() -> Weight {
+		Weight::from_ref_time(96_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(14 as u64))
+			.saturating_add(RocksDbWeight::get().writes(17 as u64))
+	}
+	fn reject_offer() -> Weight {
+		Weight::from_ref_time(36_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn start_auction() -> Weight {
+		Weight::from_ref_time(56_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+	}
+	fn bid_english() -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn bid_dutch() -> Weight {
+		Weight::from_ref_time(96_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(16 as u64))
+	}
+	fn settle_auction() -> Weight {
+		Weight::from_ref_time(90_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(15 as u64))
+	}
+	fn recall_product(n: u32, ) -> Weight {
+		Weight::from_ref_time(12_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(96_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads((14 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((16 as u64).saturating_mul(n as u64)))
+	}
+	fn reserve_recall_deposit() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn release_recall_deposit() -> Weight {
+		Weight::from_ref_time(26_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_product_owner_account() -> Weight {
+		Weight::from_ref_time(14_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn request_return() -> Weight {
+		Weight::from_ref_time(52_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	fn approve_return() -> Weight {
+		Weight::from_ref_time(154_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(14 as u64))
+			.saturating_add(RocksDbWeight::get().writes(19 as u64))
+	}
+	fn reject_return() -> Weight {
+		Weight::from_ref_time(26_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn file_warranty_claim() -> Weight {
+		Weight::from_ref_time(34_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn resolve_claim() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+}
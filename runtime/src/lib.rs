@@ -29,13 +29,13 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -103,7 +103,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -273,10 +273,69 @@ impl pallet_sudo::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	pub const AssetAccountDeposit: Balance = EXISTENTIAL_DEPOSIT;
+	pub const MetadataDepositBase: Balance = 10 * EXISTENTIAL_DEPOSIT;
+	pub const MetadataDepositPerByte: Balance = EXISTENTIAL_DEPOSIT;
+	pub const ApprovalDeposit: Balance = EXISTENTIAL_DEPOSIT;
+}
+
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const ProductResaleRoyalty: Perbill = Perbill::from_percent(5);
+	pub const ProductMarketplaceFee: Perbill = Perbill::from_percent(2);
+	pub const ProductPalletId: PalletId = PalletId(*b"py/prdct");
+	pub const ProductDeposit: Balance = 100 * EXISTENTIAL_DEPOSIT;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_product::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type AssetId = u32;
+	type Assets = Assets;
+	type PalletId = ProductPalletId;
+	type ProductDeposit = ProductDeposit;
+	type MaxNameLength = ConstU32<64>;
+	type MaxDescriptionLength = ConstU32<1024>;
+	type TransitionRules = pallet_product::traits::DefaultTransitionRules;
+	type WeightInfo = pallet_product::weights::SubstrateWeight<Runtime>;
+	type MaxHistoryLength = ConstU32<100>;
+	type ResaleRoyalty = ProductResaleRoyalty;
+	type EscrowEnabled = ConstBool<false>;
+	type EscrowTimeout = ConstU32<{ 7 * DAYS }>;
+	type MaxEscrowsPerBlock = ConstU32<50>;
+	type MaxResaleExpiriesPerBlock = ConstU32<50>;
+	type MaxOffersPerBlock = ConstU32<100>;
+	type MaxOffersPerProduct = ConstU32<20>;
+	type MaxAuctionsPerBlock = ConstU32<50>;
+	// the product owner account only receives the marketplace fee, admins hold `Role::Admin`
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type RegulatorOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxRecallBatch = ConstU32<100>;
+	type MarketplaceFee = ProductMarketplaceFee;
+	type MaxSplits = ConstU32<5>;
+	type ReturnPeriod = ConstU32<{ 14 * DAYS }>;
+	type MaxClaimsPerProduct = ConstU32<10>;
+	type ReturnResponsePeriod = ConstU32<{ 3 * DAYS }>;
+	type MaxReturnsPerBlock = ConstU32<50>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -293,6 +352,7 @@ construct_runtime!(
 		Aura: pallet_aura,
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
@@ -323,6 +383,13 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations run on runtime upgrade.
+pub type Migrations = (
+	pallet_product::migrations::v1::MigrateToV1<Runtime>,
+	pallet_product::migrations::v2::MigrateToV2<Runtime>,
+	pallet_product::migrations::v3::MigrateToV3<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -330,6 +397,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -463,6 +531,20 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_product_runtime_api::ProductApi<Block, AccountId, Balance, u32> for Runtime {
+		fn get_product(id: u128) -> Option<pallet_product::types::ProductInfo<AccountId, Balance, u32>> {
+			ProductModule::product_info(id)
+		}
+
+		fn list_by_owner(owner: AccountId) -> Vec<u128> {
+			ProductModule::products_by_owner(&owner)
+		}
+
+		fn list_by_position(kind: pallet_product::types::PositionKind) -> Vec<u128> {
+			ProductModule::products_by_position(kind)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,